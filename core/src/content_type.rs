@@ -0,0 +1,41 @@
+//! Declared payload types. Kept free of `std` so the runtime can share it.
+
+use serde::{Deserialize, Serialize};
+
+/// The declared type and codec of a payload.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum ContentType {
+    /// UTF-8 text in a single zstd frame.
+    Text = 0x01,
+    /// Opaque bytes in a single zstd frame.
+    Blob = 0x02,
+    /// WebP image (RIFF container).
+    WebP = 0x10,
+    /// AVIF image (ISO BMFF container, `avif`/`avis` brand).
+    Avif = 0x11,
+    /// AV1 video (ISO BMFF container, `av01` brand).
+    Av1 = 0x20,
+}
+
+impl ContentType {
+    /// Look up a type by its wire identifier.
+    pub fn from_id(id: u8) -> Option<Self> {
+        [
+            ContentType::Text,
+            ContentType::Blob,
+            ContentType::WebP,
+            ContentType::Avif,
+            ContentType::Av1,
+        ]
+        .into_iter()
+        .find(|t| *t as u8 == id)
+    }
+
+    /// True for types the payload module compresses itself with zstd.
+    pub fn is_zstd(&self) -> bool {
+        matches!(self, ContentType::Text | ContentType::Blob)
+    }
+}
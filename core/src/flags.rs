@@ -0,0 +1,246 @@
+//! Governance flags carried in `Manifest::gov_flags`.
+//!
+//! The low nibble is self-applied: the author sets these bits when signing the
+//! manifest. The high nibble is council-applied: these bits only take effect
+//! from a [`CouncilFlags`] statement carrying a threshold signature, and are
+//! ignored if they appear in a manifest.
+//!
+//! Reserved bits: bits 3 and 7 are unassigned. New flags are assigned from the
+//! reserved bits of the matching nibble, so a client always knows who may set a
+//! bit even before it knows its meaning. Clients must preserve unknown bits and
+//! treat them as a generic content warning.
+
+use crypto::threshold::{CombinedSignature, CouncilKeySet};
+use crypto::ContentHash;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Domain separator for council flag statements.
+const COUNCIL_FLAGS_CONTEXT: &[u8] = b"share-council-flags-v2";
+
+/// Content warning bitmask.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(transparent)]
+pub struct GovFlags(u8);
+
+bitflags::bitflags! {
+    impl GovFlags: u8 {
+        const NSFW = 1 << 0;
+        const VIOLENCE = 1 << 1;
+        const SPOILER = 1 << 2;
+
+        const COUNCIL_FLAGGED = 1 << 4;
+        const REGION_RESTRICTED = 1 << 5;
+        /// Personal data in the original was redacted by council order.
+        const PII_REDACTED = 1 << 6;
+
+        // Keep reserved bits instead of truncating them.
+        const _ = !0;
+    }
+}
+
+/// Who may set a flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagAuthority {
+    Author,
+    Council,
+}
+
+/// One entry in the flag registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlagInfo {
+    pub flag: GovFlags,
+    pub name: &'static str,
+    pub applied_by: FlagAuthority,
+}
+
+impl GovFlags {
+    /// Bits the author may set.
+    pub const SELF_APPLIED: GovFlags = GovFlags(0x0F);
+    /// Bits only the council may set.
+    pub const COUNCIL_APPLIED: GovFlags = GovFlags(0xF0);
+
+    /// Every defined flag. Bits not listed here are reserved.
+    pub const REGISTRY: &'static [FlagInfo] = &[
+        FlagInfo { flag: GovFlags::NSFW, name: "nsfw", applied_by: FlagAuthority::Author },
+        FlagInfo { flag: GovFlags::VIOLENCE, name: "violence", applied_by: FlagAuthority::Author },
+        FlagInfo { flag: GovFlags::SPOILER, name: "spoiler", applied_by: FlagAuthority::Author },
+        FlagInfo { flag: GovFlags::COUNCIL_FLAGGED, name: "council-flagged", applied_by: FlagAuthority::Council },
+        FlagInfo { flag: GovFlags::REGION_RESTRICTED, name: "region-restricted", applied_by: FlagAuthority::Council },
+        FlagInfo { flag: GovFlags::PII_REDACTED, name: "pii-redacted", applied_by: FlagAuthority::Council },
+    ];
+
+    pub fn self_applied(&self) -> GovFlags {
+        *self & Self::SELF_APPLIED
+    }
+
+    pub fn council_applied(&self) -> GovFlags {
+        *self & Self::COUNCIL_APPLIED
+    }
+
+    /// Set bits that are not in the registry.
+    pub fn unknown(&self) -> GovFlags {
+        let known = Self::REGISTRY.iter().fold(GovFlags::empty(), |acc, info| acc | info.flag);
+        *self - known
+    }
+
+    /// Registry names of the set flags.
+    pub fn names(&self) -> Vec<&'static str> {
+        Self::REGISTRY
+            .iter()
+            .filter(|info| self.contains(info.flag))
+            .map(|info| info.name)
+            .collect()
+    }
+
+    /// Fails if an author tried to set council bits.
+    pub fn check_self_applied(&self) -> Result<(), FlagError> {
+        let council = self.council_applied();
+        if council.is_empty() {
+            Ok(())
+        } else {
+            Err(FlagError::CouncilBitsSelfApplied(council))
+        }
+    }
+}
+
+/// Reasons flags are refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlagError {
+    /// A manifest sets bits reserved for the council.
+    CouncilBitsSelfApplied(GovFlags),
+    /// A council statement sets author bits.
+    SelfBitsInCouncilStatement(GovFlags),
+    InvalidCouncilSignature,
+}
+
+impl fmt::Display for FlagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlagError::CouncilBitsSelfApplied(flags) => {
+                write!(f, "Council-only flags {:#04x} set by author", flags.bits())
+            }
+            FlagError::SelfBitsInCouncilStatement(flags) => {
+                write!(f, "Author-only flags {:#04x} set by council statement", flags.bits())
+            }
+            FlagError::InvalidCouncilSignature => write!(f, "Council flag statement has an invalid signature"),
+        }
+    }
+}
+
+impl std::error::Error for FlagError {}
+
+/// Council-applied flags for one piece of content, threshold-signed.
+///
+/// Each statement replaces the previous one for the same content: the council
+/// lifts a flag by signing a statement with a higher `sequence` without it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CouncilFlags {
+    pub content_hash: ContentHash,
+    /// Increases with every statement the council signs for this content.
+    pub sequence: u64,
+    /// Council bits only.
+    pub flags: GovFlags,
+    pub signature: CombinedSignature,
+}
+
+impl CouncilFlags {
+    /// The message council members sign.
+    pub fn signing_bytes(content_hash: &ContentHash, sequence: u64, flags: GovFlags) -> Vec<u8> {
+        let mut message = COUNCIL_FLAGS_CONTEXT.to_vec();
+        message.extend_from_slice(content_hash.as_bytes());
+        message.extend_from_slice(&sequence.to_le_bytes());
+        message.push(flags.bits());
+        message
+    }
+
+    /// The statement in force for `content_hash`: the valid one with the
+    /// highest sequence, whatever order `statements` come in.
+    pub fn latest<'a>(
+        statements: &'a [CouncilFlags],
+        content_hash: &ContentHash,
+        council: &CouncilKeySet,
+    ) -> Option<&'a CouncilFlags> {
+        statements
+            .iter()
+            .filter(|s| s.content_hash == *content_hash && s.verify(council).is_ok())
+            .max_by_key(|s| (s.sequence, s.flags.bits()))
+    }
+
+    pub fn verify(&self, council: &CouncilKeySet) -> Result<(), FlagError> {
+        let self_bits = self.flags.self_applied();
+        if !self_bits.is_empty() {
+            return Err(FlagError::SelfBitsInCouncilStatement(self_bits));
+        }
+        if !council.verify(&Self::signing_bytes(&self.content_hash, self.sequence, self.flags), &self.signature) {
+            return Err(FlagError::InvalidCouncilSignature);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::threshold::PartialVote;
+    use threshold_crypto::SecretKeySet;
+
+    /// A single-member council, so one share is a full signature.
+    fn council() -> (SecretKeySet, CouncilKeySet) {
+        let keys = SecretKeySet::random(0, &mut rand::thread_rng());
+        let council = CouncilKeySet::try_from(keys.public_keys()).unwrap();
+        (keys, council)
+    }
+
+    fn statement(
+        keys: &SecretKeySet,
+        council: &CouncilKeySet,
+        content_hash: ContentHash,
+        sequence: u64,
+        flags: GovFlags,
+    ) -> CouncilFlags {
+        let share = keys.secret_key_share(0).sign(CouncilFlags::signing_bytes(&content_hash, sequence, flags));
+        let vote = PartialVote::from_bytes(&share.to_bytes()).unwrap();
+        let signature = CombinedSignature::combine(council, vec![(0, &vote)]).unwrap();
+        CouncilFlags { content_hash, sequence, flags, signature }
+    }
+
+    #[test]
+    fn test_self_and_council_bits_are_separate() {
+        let author = GovFlags::NSFW | GovFlags::SPOILER;
+        assert!(author.check_self_applied().is_ok());
+        assert_eq!(author.names(), vec!["nsfw", "spoiler"]);
+
+        let forged = author | GovFlags::COUNCIL_FLAGGED;
+        assert_eq!(
+            forged.check_self_applied(),
+            Err(FlagError::CouncilBitsSelfApplied(GovFlags::COUNCIL_FLAGGED))
+        );
+        assert_eq!(forged.self_applied(), author);
+    }
+
+    #[test]
+    fn test_reserved_bits_survive_roundtrip() {
+        let flags: GovFlags = serde_json::from_str("137").unwrap(); // NSFW | bit 3 | bit 7
+        assert_eq!(flags.bits(), 137);
+        assert_eq!(flags.unknown().bits(), 0x88);
+        assert_eq!(serde_json::to_string(&flags).unwrap(), "137");
+    }
+
+    #[test]
+    fn test_latest_statement_wins_in_any_order() {
+        let (keys, council) = council();
+        let hash = ContentHash::hash(b"post");
+        let flagged = statement(&keys, &council, hash, 1, GovFlags::COUNCIL_FLAGGED | GovFlags::PII_REDACTED);
+        let lifted = statement(&keys, &council, hash, 2, GovFlags::PII_REDACTED);
+
+        for statements in [[flagged.clone(), lifted.clone()], [lifted, flagged]] {
+            let latest = CouncilFlags::latest(&statements, &hash, &council).unwrap();
+            assert_eq!(latest.flags, GovFlags::PII_REDACTED);
+        }
+
+        let mut replayed = statement(&keys, &council, hash, 1, GovFlags::COUNCIL_FLAGGED);
+        replayed.sequence = 3;
+        assert_eq!(replayed.verify(&council), Err(FlagError::InvalidCouncilSignature));
+    }
+}
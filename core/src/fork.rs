@@ -0,0 +1,544 @@
+//! Fork identity and ratification (GOVERNANCE_CONSTITUTION.md Art. IV).
+//!
+//! A network advertises the `ForkDescriptor` it runs. Clients only connect to
+//! peers on a fork they hold a valid ratification for, either threshold-signed
+//! by a council of at least ten members or carried by a community vote that
+//! overrode a council veto.
+//!
+//! A community ratification carries no council signature. Its proof is the
+//! on-chain record of the vote (`governance::ratification::CommunityRecord`):
+//! the fork it ratified, the council veto it overrode, the tally and a Merkle
+//! root over the ballots, plus the finalized state root it was read under
+//! and a storage proof of the record under that root. The chain only writes
+//! the record once the vote has overridden a rejected council ratification of
+//! the same fork; the client's light client vouches for the state root, the
+//! storage proof binds the record to it, and the ballots can be audited
+//! against the record's root.
+
+use crate::state_proof::{self, StateProofError};
+use crypto::merkle::{MerkleProof, MerkleTree};
+use crypto::threshold::{CombinedSignature, CouncilKeySet};
+use crypto::wire::{tag, WireCodec, WireReader, WireWriter};
+use crypto::{ContentHash, CryptoError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Charter: forks need approval by a council of at least ten members.
+pub const MIN_RATIFYING_COUNCIL: u32 = 10;
+
+/// Domain separator for council ratification signatures.
+const RATIFICATION_CONTEXT: &[u8] = b"share-fork-ratification-v1";
+
+/// Where the governance pallet stores community ratification records, by fork.
+const RECORDS_PALLET: &str = "Governance";
+const RECORDS_STORAGE: &str = "CommunityRatifications";
+
+/// What identifies a network: its chain, runtime and client protocol.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ForkDescriptor {
+    pub genesis_hash: [u8; 32],
+    /// Hash of the runtime code blob.
+    pub runtime_code_hash: [u8; 32],
+    pub protocol_version: u16,
+    /// The fork this one derives from; `None` for the original network.
+    pub parent: Option<ContentHash>,
+}
+
+impl ForkDescriptor {
+    /// BLAKE3 hash of the wire encoding; ratifications and peers refer to a
+    /// fork by this id.
+    pub fn id(&self) -> ContentHash {
+        ContentHash::hash(&self.to_wire().expect("fork descriptor fields are fixed size"))
+    }
+}
+
+impl WireCodec for ForkDescriptor {
+    const TAG: u8 = tag::FORK_DESCRIPTOR;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.raw(&self.genesis_hash);
+        writer.raw(&self.runtime_code_hash);
+        writer.u16(self.protocol_version);
+        match &self.parent {
+            None => writer.u8(0),
+            Some(parent) => {
+                writer.u8(1);
+                writer.raw(parent.as_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        Ok(ForkDescriptor {
+            genesis_hash: reader.array()?,
+            runtime_code_hash: reader.array()?,
+            protocol_version: reader.u16()?,
+            parent: match reader.u8()? {
+                0 => None,
+                1 => Some(ContentHash::from_bytes(reader.array()?)),
+                flag => return Err(CryptoError::DecodingError(format!("Invalid option flag {}", flag))),
+            },
+        })
+    }
+}
+
+/// Evidence that a fork was ratified.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum Ratification {
+    /// Threshold-signed by the council over `signing_bytes(fork, council_size)`.
+    Council { council_size: u32, signature: CombinedSignature },
+    /// Carried by a community vote overriding a council veto (Art. IV §2).
+    Community(CommunityProof),
+}
+
+impl Ratification {
+    /// The message council members sign. The council size is signed too, so
+    /// it cannot be inflated afterwards.
+    pub fn signing_bytes(fork: &ContentHash, council_size: u32) -> Vec<u8> {
+        [RATIFICATION_CONTEXT, fork.as_bytes(), &council_size.to_le_bytes()].concat()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    Yes = 0,
+    No = 1,
+    Abstain = 2,
+}
+
+/// One community ballot, as committed to in `CommunityProof::ballots_root`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommunityBallot {
+    /// On-chain account id of the voter.
+    pub voter: [u8; 32],
+    pub choice: Choice,
+}
+
+impl CommunityBallot {
+    /// Merkle leaf: `voter || choice`. Leaves are ordered by voter.
+    pub fn leaf(&self) -> [u8; 33] {
+        let mut leaf = [0u8; 33];
+        leaf[..32].copy_from_slice(&self.voter);
+        leaf[32] = self.choice as u8;
+        leaf
+    }
+}
+
+/// Result of a passed community ratification, as recorded on chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CommunityProof {
+    /// The ratified fork, i.e. the payload hash of both proposals.
+    pub fork: ContentHash,
+    /// Id of the community proposal.
+    pub proposal: u64,
+    /// When the community proposal was opened.
+    pub opened_at: u64,
+    /// Id of the council proposal that refused the fork.
+    pub vetoed_by: u64,
+    /// When the council vote closed.
+    pub vetoed_at: u64,
+    pub yes: u64,
+    pub no: u64,
+    pub abstain: u64,
+    /// Merkle root over every ballot's `leaf()`, ordered by voter.
+    pub ballots_root: ContentHash,
+    /// Finalized state root the proposal and veto were read under.
+    pub state_root: [u8; 32],
+    /// Trie nodes proving the record under `state_root`.
+    pub storage_proof: Vec<Vec<u8>>,
+}
+
+impl CommunityProof {
+    /// Build the proof from the full ballot list, without a storage proof.
+    pub fn from_ballots(
+        fork: ContentHash,
+        proposal: u64,
+        opened_at: u64,
+        vetoed_by: u64,
+        vetoed_at: u64,
+        ballots: &[CommunityBallot],
+        state_root: [u8; 32],
+    ) -> Self {
+        let count = |choice| ballots.iter().filter(|b| b.choice == choice).count() as u64;
+        CommunityProof {
+            fork,
+            proposal,
+            opened_at,
+            vetoed_by,
+            vetoed_at,
+            yes: count(Choice::Yes),
+            no: count(Choice::No),
+            abstain: count(Choice::Abstain),
+            ballots_root: Self::root_of(ballots),
+            state_root,
+            storage_proof: Vec::new(),
+        }
+    }
+
+    /// Attach the trie nodes proving the record under `state_root`.
+    pub fn with_storage_proof(mut self, nodes: Vec<Vec<u8>>) -> Self {
+        self.storage_proof = nodes;
+        self
+    }
+
+    /// The record as the chain stores it: the SCALE encoding of
+    /// `governance::ratification::CommunityRecord`.
+    pub fn record_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(120);
+        bytes.extend_from_slice(self.fork.as_bytes());
+        for field in [self.proposal, self.opened_at, self.vetoed_by, self.vetoed_at, self.yes, self.no, self.abstain] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+        bytes.extend_from_slice(self.ballots_root.as_bytes());
+        bytes
+    }
+
+    /// Check the storage proof: the chain stored exactly this record for the
+    /// fork under `state_root`.
+    pub fn verify_state(&self) -> Result<(), ForkError> {
+        let key = state_proof::map_key(RECORDS_PALLET, RECORDS_STORAGE, self.fork.as_bytes());
+        let stored = state_proof::read(&self.state_root, &self.storage_proof, &key).map_err(ForkError::StateProof)?;
+        if stored.as_deref() != Some(self.record_bytes().as_slice()) {
+            return Err(ForkError::RecordMismatch);
+        }
+        Ok(())
+    }
+
+    /// Art. IV §2: more than half of the votes cast for or against.
+    pub fn passed(&self) -> bool {
+        self.yes > self.no
+    }
+
+    /// The veto must be a different, earlier proposal: the community vote
+    /// was opened only after the council vote closed.
+    pub fn follows_veto(&self) -> bool {
+        self.vetoed_by != self.proposal && self.vetoed_at <= self.opened_at
+    }
+
+    /// Check a single ballot against the commitment.
+    pub fn verify_ballot(&self, ballot: &CommunityBallot, proof: &MerkleProof) -> bool {
+        proof.leaf_count == self.yes + self.no + self.abstain && proof.verify(&self.ballots_root, &ballot.leaf())
+    }
+
+    /// Recount the full ballot list and check it reproduces the proof.
+    pub fn audit(&self, ballots: &[CommunityBallot]) -> Result<(), ForkError> {
+        let voters: HashSet<[u8; 32]> = ballots.iter().map(|b| b.voter).collect();
+        if voters.len() != ballots.len() {
+            return Err(ForkError::BallotMismatch);
+        }
+        let recount = Self::from_ballots(
+            self.fork,
+            self.proposal,
+            self.opened_at,
+            self.vetoed_by,
+            self.vetoed_at,
+            ballots,
+            self.state_root,
+        )
+        .with_storage_proof(self.storage_proof.clone());
+        if recount != *self {
+            return Err(ForkError::BallotMismatch);
+        }
+        Ok(())
+    }
+
+    fn root_of(ballots: &[CommunityBallot]) -> ContentHash {
+        let mut ballots = ballots.to_vec();
+        ballots.sort_by_key(|b| b.voter);
+        MerkleTree::new(ballots.iter().map(CommunityBallot::leaf)).root()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RatifiedFork {
+    pub descriptor: ForkDescriptor,
+    pub ratification: Ratification,
+}
+
+impl RatifiedFork {
+    /// Check the ratification. `council` is needed for council ratifications;
+    /// a community ratification is checked against its state root, which the
+    /// caller must know to be finalized.
+    pub fn verify(&self, council: Option<&CouncilKeySet>) -> Result<(), ForkError> {
+        match &self.ratification {
+            Ratification::Council { council_size, signature } => {
+                if *council_size < MIN_RATIFYING_COUNCIL {
+                    return Err(ForkError::CouncilTooSmall(*council_size));
+                }
+                let council = council.ok_or(ForkError::NoCouncilKey)?;
+                let message = Ratification::signing_bytes(&self.descriptor.id(), *council_size);
+                if !council.verify(&message, signature) {
+                    return Err(ForkError::InvalidSignature);
+                }
+            }
+            Ratification::Community(proof) => {
+                if proof.fork != self.descriptor.id() {
+                    return Err(ForkError::ForkMismatch(proof.fork));
+                }
+                if !proof.follows_veto() {
+                    return Err(ForkError::VetoMismatch);
+                }
+                if !proof.passed() {
+                    return Err(ForkError::VoteNotPassed { yes: proof.yes, no: proof.no });
+                }
+                proof.verify_state()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForkError {
+    NotRatified(ContentHash),
+    /// The parent fork is neither the trusted root nor ratified.
+    UnknownParent(ContentHash),
+    CouncilTooSmall(u32),
+    /// Council ratifications cannot be checked without the council key set.
+    NoCouncilKey,
+    InvalidSignature,
+    /// The community proof ratifies a different fork.
+    ForkMismatch(ContentHash),
+    /// The community proof does not follow an earlier council veto.
+    VetoMismatch,
+    VoteNotPassed { yes: u64, no: u64 },
+    /// The community proof's state root is not one the client saw finalized.
+    UnfinalizedState,
+    /// The storage proof cannot be read under the state root.
+    StateProof(StateProofError),
+    /// The state root holds no record, or a different one, for the fork.
+    RecordMismatch,
+    /// The ballots do not reproduce the community proof.
+    BallotMismatch,
+}
+
+impl fmt::Display for ForkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForkError::NotRatified(id) => write!(f, "Fork {} is not ratified", id),
+            ForkError::UnknownParent(id) => write!(f, "Parent fork {} is not ratified", id),
+            ForkError::CouncilTooSmall(size) => {
+                write!(f, "Ratifying council has {} members, needs {}", size, MIN_RATIFYING_COUNCIL)
+            }
+            ForkError::NoCouncilKey => write!(f, "No council key set to verify the ratification"),
+            ForkError::InvalidSignature => write!(f, "Ratification has an invalid council signature"),
+            ForkError::ForkMismatch(id) => write!(f, "Community proof ratifies fork {}", id),
+            ForkError::VetoMismatch => write!(f, "Community vote does not follow a council veto"),
+            ForkError::VoteNotPassed { yes, no } => {
+                write!(f, "Community vote did not pass ({} yes, {} no)", yes, no)
+            }
+            ForkError::UnfinalizedState => write!(f, "Community proof refers to an unfinalized state root"),
+            ForkError::StateProof(err) => write!(f, "Invalid community record proof: {}", err),
+            ForkError::RecordMismatch => write!(f, "Community proof does not match the record on chain"),
+            ForkError::BallotMismatch => write!(f, "Ballots do not match the community proof"),
+        }
+    }
+}
+
+impl std::error::Error for ForkError {}
+
+/// Which forks a client will talk to.
+#[derive(Debug, Clone)]
+pub struct ForkPolicy {
+    council: Option<CouncilKeySet>,
+    root: ContentHash,
+    accepted: HashMap<ContentHash, ForkDescriptor>,
+    finalized: HashSet<[u8; 32]>,
+}
+
+impl ForkPolicy {
+    /// Trust the original network described by `root`.
+    pub fn new(root: ForkDescriptor) -> Self {
+        let id = root.id();
+        ForkPolicy { council: None, root: id, accepted: HashMap::from([(id, root)]), finalized: HashSet::new() }
+    }
+
+    /// The council key set used to check council ratifications.
+    pub fn with_council(mut self, council: CouncilKeySet) -> Self {
+        self.council = Some(council);
+        self
+    }
+
+    /// Record a state root the client's light client has seen finalized.
+    pub fn note_finalized(&mut self, state_root: [u8; 32]) {
+        self.finalized.insert(state_root);
+    }
+
+    /// Accept a ratified fork. Its parent must already be accepted, so every
+    /// accepted fork descends from the root through ratified forks.
+    pub fn ratify(&mut self, fork: RatifiedFork) -> Result<ContentHash, ForkError> {
+        fork.verify(self.council.as_ref())?;
+        if let Ratification::Community(proof) = &fork.ratification {
+            if !self.finalized.contains(&proof.state_root) {
+                return Err(ForkError::UnfinalizedState);
+            }
+        }
+        let parent = fork.descriptor.parent.ok_or(ForkError::UnknownParent(self.root))?;
+        if !self.accepted.contains_key(&parent) {
+            return Err(ForkError::UnknownParent(parent));
+        }
+        let id = fork.descriptor.id();
+        self.accepted.insert(id, fork.descriptor);
+        Ok(id)
+    }
+
+    /// Whether a peer advertising `fork` may be connected to.
+    pub fn check_peer(&self, fork: &ForkDescriptor) -> Result<(), ForkError> {
+        let id = fork.id();
+        if !self.accepted.contains_key(&id) {
+            return Err(ForkError::NotRatified(id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor(version: u16, parent: Option<ContentHash>) -> ForkDescriptor {
+        ForkDescriptor { genesis_hash: [1; 32], runtime_code_hash: [version as u8; 32], protocol_version: version, parent }
+    }
+
+    fn ballots(yes: u8, no: u8) -> Vec<CommunityBallot> {
+        let choice = |i| if i < yes { Choice::Yes } else { Choice::No };
+        (0..yes + no).map(|i| CommunityBallot { voter: [i; 32], choice: choice(i) }).collect()
+    }
+
+    /// Proof of the record in a state holding nothing else.
+    fn proof(fork: &ForkDescriptor, cast: &[CommunityBallot]) -> CommunityProof {
+        let unproven = CommunityProof::from_ballots(fork.id(), 1, 20, 0, 10, cast, [0; 32]);
+        let key = state_proof::map_key(RECORDS_PALLET, RECORDS_STORAGE, unproven.fork.as_bytes());
+        let (state_root, nodes) = state_proof::single_entry(&key, &unproven.record_bytes());
+        CommunityProof { state_root, ..unproven }.with_storage_proof(nodes)
+    }
+
+    fn community(descriptor: ForkDescriptor, yes: u8, no: u8) -> RatifiedFork {
+        let proof = proof(&descriptor, &ballots(yes, no));
+        RatifiedFork { descriptor, ratification: Ratification::Community(proof) }
+    }
+
+    fn state_root(fork: &RatifiedFork) -> [u8; 32] {
+        match &fork.ratification {
+            Ratification::Community(proof) => proof.state_root,
+            Ratification::Council { .. } => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_policy_follows_ratified_lineage() {
+        let root = descriptor(1, None);
+        let mut policy = ForkPolicy::new(root.clone());
+        assert!(policy.check_peer(&root).is_ok());
+
+        let child = descriptor(2, Some(root.id()));
+        assert_eq!(policy.check_peer(&child), Err(ForkError::NotRatified(child.id())));
+        assert_eq!(policy.ratify(community(child.clone(), 5, 5)), Err(ForkError::VoteNotPassed { yes: 5, no: 5 }));
+
+        // All-zero bytes are not a valid G2 encoding; the compressed point at
+        // infinity is, and the missing key is reported before it is checked.
+        let mut infinity = [0; 96];
+        infinity[0] = 0xC0;
+        let council = RatifiedFork {
+            descriptor: child.clone(),
+            ratification: Ratification::Council {
+                council_size: 10,
+                signature: CombinedSignature::from_bytes(&infinity).unwrap(),
+            },
+        };
+        assert_eq!(policy.ratify(council), Err(ForkError::NoCouncilKey));
+
+        assert_eq!(policy.ratify(community(child.clone(), 6, 5)), Err(ForkError::UnfinalizedState));
+        policy.note_finalized(state_root(&community(child.clone(), 6, 5)));
+        assert_eq!(policy.ratify(community(child.clone(), 6, 5)), Ok(child.id()));
+        assert!(policy.check_peer(&child).is_ok());
+
+        let orphan = community(descriptor(3, Some(ContentHash::hash(b"elsewhere"))), 1, 0);
+        policy.note_finalized(state_root(&orphan));
+        assert!(matches!(policy.ratify(orphan), Err(ForkError::UnknownParent(_))));
+    }
+
+    #[test]
+    fn test_community_proof_is_bound_to_fork_and_veto() {
+        let root = descriptor(1, None);
+        let fork_a = descriptor(2, Some(root.id()));
+        let fork_b = descriptor(3, Some(root.id()));
+        let mut policy = ForkPolicy::new(root);
+        let for_a = proof(&fork_a, &ballots(6, 5));
+        policy.note_finalized(for_a.state_root);
+
+        let stolen = RatifiedFork { descriptor: fork_b.clone(), ratification: Ratification::Community(for_a.clone()) };
+        assert_eq!(policy.ratify(stolen), Err(ForkError::ForkMismatch(fork_a.id())));
+        assert_eq!(policy.check_peer(&fork_b), Err(ForkError::NotRatified(fork_b.id())));
+
+        let before_veto = CommunityProof { opened_at: 5, ..for_a.clone() };
+        let self_veto = CommunityProof { vetoed_by: 1, ..for_a.clone() };
+        for proof in [before_veto, self_veto] {
+            let fork = RatifiedFork { descriptor: fork_a.clone(), ratification: Ratification::Community(proof) };
+            assert_eq!(policy.ratify(fork), Err(ForkError::VetoMismatch));
+        }
+        let fork = RatifiedFork { descriptor: fork_a.clone(), ratification: Ratification::Community(for_a) };
+        assert_eq!(policy.ratify(fork), Ok(fork_a.id()));
+    }
+
+    #[test]
+    fn test_community_proof_is_bound_to_finalized_record() {
+        let root = descriptor(1, None);
+        let fork_a = descriptor(2, Some(root.id()));
+        let fork_b = descriptor(3, Some(root.id()));
+        let mut policy = ForkPolicy::new(root);
+        let recorded = proof(&fork_a, &ballots(6, 5));
+        policy.note_finalized(recorded.state_root);
+        let ratify = |policy: &mut ForkPolicy, descriptor: &ForkDescriptor, proof: CommunityProof| {
+            policy.ratify(RatifiedFork { descriptor: descriptor.clone(), ratification: Ratification::Community(proof) })
+        };
+
+        // Fields that differ from the record the state root holds.
+        let inflated = CommunityProof { yes: 9, ..recorded.clone() };
+        assert_eq!(ratify(&mut policy, &fork_a, inflated), Err(ForkError::RecordMismatch));
+        let reballoted = CommunityProof { ballots_root: ContentHash::hash(b"other ballots"), ..recorded.clone() };
+        assert_eq!(ratify(&mut policy, &fork_a, reballoted), Err(ForkError::RecordMismatch));
+
+        // A fork the chain never recorded, with a fabricated trie claiming the
+        // finalized root, or with the genuine nodes.
+        let fabricated = proof(&fork_b, &ballots(6, 5));
+        let forged = CommunityProof { state_root: recorded.state_root, ..fabricated.clone() };
+        assert_eq!(
+            ratify(&mut policy, &fork_b, forged),
+            Err(ForkError::StateProof(StateProofError::MissingNode(recorded.state_root)))
+        );
+        let borrowed = CommunityProof {
+            state_root: recorded.state_root,
+            storage_proof: recorded.storage_proof.clone(),
+            ..fabricated.clone()
+        };
+        assert_eq!(ratify(&mut policy, &fork_b, borrowed), Err(ForkError::RecordMismatch));
+        assert_eq!(ratify(&mut policy, &fork_b, fabricated), Err(ForkError::UnfinalizedState));
+
+        let unproven = recorded.clone().with_storage_proof(Vec::new());
+        assert!(matches!(ratify(&mut policy, &fork_a, unproven), Err(ForkError::StateProof(_))));
+        assert_eq!(ratify(&mut policy, &fork_a, recorded), Ok(fork_a.id()));
+    }
+
+    #[test]
+    fn test_community_proof_audit() {
+        let mut cast = ballots(3, 2);
+        let proof = proof(&descriptor(2, None), &cast);
+        cast.reverse();
+        assert!(proof.audit(&cast).is_ok());
+
+        let tree = MerkleTree::new(ballots(3, 2).iter().map(CommunityBallot::leaf));
+        assert!(proof.verify_ballot(&ballots(3, 2)[1], &tree.proof(1).unwrap()));
+
+        cast[0].choice = Choice::Yes;
+        assert_eq!(proof.audit(&cast), Err(ForkError::BallotMismatch));
+    }
+
+    #[test]
+    fn test_descriptor_wire_roundtrip() {
+        let fork = descriptor(2, Some(ContentHash::hash(b"parent")));
+        assert_eq!(ForkDescriptor::from_wire(&fork.to_wire().unwrap()).unwrap(), fork);
+    }
+}
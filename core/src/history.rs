@@ -0,0 +1,232 @@
+//! Post editing and retraction.
+//!
+//! Ciphertext and manifests are immutable, so an edit is a new manifest plus an
+//! author-signed [`ManifestUpdate`] pointing from the old manifest to the new
+//! one. A [`Retraction`] withdraws the whole post. [`resolve`] turns whatever
+//! records a client has collected into one linear history.
+
+use crate::manifest::Manifest;
+use crypto::signatures::UserSecret;
+use crypto::{ContentHash, CryptoError, IdentityKey};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+/// Domain separators for update and retraction signatures.
+const UPDATE_CONTEXT: &[u8] = b"share-manifest-update-v1";
+const RETRACTION_CONTEXT: &[u8] = b"share-retraction-v1";
+
+/// Author-signed link from a manifest to the manifest that supersedes it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestUpdate {
+    pub author: IdentityKey,
+    /// Hash of the superseded manifest.
+    pub previous: ContentHash,
+    /// Hash of the new manifest.
+    pub next: ContentHash,
+    #[serde(with = "serde_bytes")]
+    pub sig: [u8; 64],
+}
+
+impl ManifestUpdate {
+    pub fn sign(previous: &Manifest, next: &Manifest, secret: &UserSecret) -> Self {
+        let (previous, next) = (previous.hash(), next.hash());
+        ManifestUpdate {
+            author: secret.public_key(),
+            sig: secret.sign(&update_message(&previous, &next)),
+            previous,
+            next,
+        }
+    }
+
+    pub fn verify(&self) -> Result<(), CryptoError> {
+        self.author.verify(&update_message(&self.previous, &self.next), &self.sig)
+    }
+}
+
+/// Author-signed withdrawal of a post. Retracting any version retracts the post.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Retraction {
+    pub author: IdentityKey,
+    pub manifest: ContentHash,
+    #[serde(with = "serde_bytes")]
+    pub sig: [u8; 64],
+}
+
+impl Retraction {
+    pub fn sign(manifest: &Manifest, secret: &UserSecret) -> Self {
+        let manifest = manifest.hash();
+        Retraction {
+            author: secret.public_key(),
+            sig: secret.sign(&retraction_message(&manifest)),
+            manifest,
+        }
+    }
+
+    pub fn verify(&self) -> Result<(), CryptoError> {
+        self.author.verify(&retraction_message(&self.manifest), &self.sig)
+    }
+}
+
+/// The resolved versions of one post.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostHistory {
+    /// Every version, oldest first. Never empty.
+    pub versions: Vec<Manifest>,
+    pub retraction: Option<Retraction>,
+}
+
+impl PostHistory {
+    /// The original manifest.
+    pub fn root(&self) -> &Manifest {
+        &self.versions[0]
+    }
+
+    /// The latest version, or `None` once the post is retracted.
+    pub fn current(&self) -> Option<&Manifest> {
+        match self.retraction {
+            Some(_) => None,
+            None => self.versions.last(),
+        }
+    }
+
+    pub fn is_retracted(&self) -> bool {
+        self.retraction.is_some()
+    }
+}
+
+/// Reasons a post's history cannot be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    /// The root manifest is not in the given set.
+    UnknownManifest(ContentHash),
+    /// The root manifest's signature does not verify.
+    InvalidSignature(ContentHash),
+    /// The author signed two different successors of this manifest.
+    Fork(ContentHash),
+    /// Following updates leads back to this manifest.
+    Cycle(ContentHash),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::UnknownManifest(h) => write!(f, "Manifest {} not found", h),
+            HistoryError::InvalidSignature(h) => write!(f, "Manifest {} has an invalid signature", h),
+            HistoryError::Fork(h) => write!(f, "Manifest {} has more than one successor", h),
+            HistoryError::Cycle(h) => write!(f, "Update chain loops back to manifest {}", h),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+/// Resolve the history of the post whose first manifest is `root`.
+///
+/// Only records signed by the root's author count: updates and retractions
+/// by anyone else, with bad signatures, or pointing at manifests not in
+/// `manifests` (or not by the author) are ignored, so a third party cannot
+/// hijack or block a post by publishing records about it. The author's own
+/// records must form a single chain.
+pub fn resolve(
+    root: &ContentHash,
+    manifests: &[Manifest],
+    updates: &[ManifestUpdate],
+    retractions: &[Retraction],
+) -> Result<PostHistory, HistoryError> {
+    let by_hash: HashMap<ContentHash, &Manifest> = manifests.iter().map(|m| (m.hash(), m)).collect();
+
+    let first = by_hash.get(root).ok_or(HistoryError::UnknownManifest(*root))?;
+    first.verify().map_err(|_| HistoryError::InvalidSignature(*root))?;
+    let author = first.auth_id;
+
+    let by_author = |hash: &ContentHash| {
+        by_hash
+            .get(hash)
+            .filter(|m| m.auth_id == author && m.verify().is_ok())
+            .copied()
+    };
+
+    // Successors of each manifest, deduplicated
+    let mut successors: HashMap<ContentHash, BTreeSet<[u8; 32]>> = HashMap::new();
+    for update in updates {
+        if update.author == author && update.verify().is_ok() && by_author(&update.next).is_some() {
+            successors.entry(update.previous).or_default().insert(*update.next.as_bytes());
+        }
+    }
+
+    let mut versions = vec![(*first).clone()];
+    let mut seen = HashSet::from([*root]);
+    let mut current = *root;
+    while let Some(next) = successors.get(&current) {
+        if next.len() > 1 {
+            return Err(HistoryError::Fork(current));
+        }
+        let next = ContentHash::from_bytes(*next.first().expect("entries are never empty"));
+        if !seen.insert(next) {
+            return Err(HistoryError::Cycle(next));
+        }
+        versions.push(by_author(&next).expect("checked when collecting updates").clone());
+        current = next;
+    }
+
+    let retraction = retractions
+        .iter()
+        .find(|r| r.author == author && seen.contains(&r.manifest) && r.verify().is_ok())
+        .cloned();
+
+    Ok(PostHistory { versions, retraction })
+}
+
+fn update_message(previous: &ContentHash, next: &ContentHash) -> Vec<u8> {
+    [UPDATE_CONTEXT, previous.as_bytes(), next.as_bytes()].concat()
+}
+
+fn retraction_message(manifest: &ContentHash) -> Vec<u8> {
+    [RETRACTION_CONTEXT, manifest.as_bytes()].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_manifest;
+
+    fn manifest(secret: &UserSecret, body: &[u8]) -> Manifest {
+        test_manifest(secret, body, |_| {})
+    }
+
+    #[test]
+    fn test_resolve_ignores_foreign_records() {
+        let alice = UserSecret::generate();
+        let mallory = UserSecret::generate();
+        let v1 = manifest(&alice, b"helo");
+        let v2 = manifest(&alice, b"hello");
+        let hijack = manifest(&mallory, b"spam");
+
+        let manifests = [v1.clone(), v2.clone(), hijack.clone()];
+        let updates = [ManifestUpdate::sign(&v1, &v2, &alice), ManifestUpdate::sign(&v1, &hijack, &mallory)];
+
+        let history = resolve(&v1.hash(), &manifests, &updates, &[Retraction::sign(&v2, &mallory)]).unwrap();
+        assert_eq!(history.versions, vec![v1.clone(), v2.clone()]);
+        assert_eq!(history.current(), Some(&v2));
+
+        let retracted = resolve(&v1.hash(), &manifests, &updates, &[Retraction::sign(&v2, &alice)]).unwrap();
+        assert!(retracted.is_retracted());
+        assert_eq!(retracted.current(), None);
+    }
+
+    #[test]
+    fn test_resolve_rejects_forks_and_cycles() {
+        let alice = UserSecret::generate();
+        let v1 = manifest(&alice, b"one");
+        let v2 = manifest(&alice, b"two");
+        let v3 = manifest(&alice, b"three");
+        let manifests = [v1.clone(), v2.clone(), v3.clone()];
+
+        let fork = [ManifestUpdate::sign(&v1, &v2, &alice), ManifestUpdate::sign(&v1, &v3, &alice)];
+        assert_eq!(resolve(&v1.hash(), &manifests, &fork, &[]), Err(HistoryError::Fork(v1.hash())));
+
+        let cycle = [ManifestUpdate::sign(&v1, &v2, &alice), ManifestUpdate::sign(&v2, &v1, &alice)];
+        assert_eq!(resolve(&v1.hash(), &manifests, &cycle, &[]), Err(HistoryError::Cycle(v1.hash())));
+    }
+}
@@ -1,8 +1,37 @@
-//! Core module: account lifecycle, payload spec, manifests
+//! Core module: account lifecycle, payload spec, manifests, summaries, licenses, flags, forks, edit history, threads, regions, chunk storage, state proofs, technical limits
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+pub mod content_type;
+pub mod limits;
+
+#[cfg(feature = "std")]
 pub mod account;
+#[cfg(feature = "std")]
+pub mod flags;
+#[cfg(feature = "std")]
+pub mod fork;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod license;
+#[cfg(feature = "std")]
+pub mod manifest;
+#[cfg(feature = "std")]
 pub mod payload;
+#[cfg(feature = "std")]
+pub mod region;
+#[cfg(feature = "std")]
+pub mod state_proof;
+#[cfg(feature = "std")]
+pub mod store;
+#[cfg(feature = "std")]
+pub mod summary;
+#[cfg(feature = "std")]
+pub mod thread;
 
+#[cfg(feature = "std")]
 pub fn init() {
     println!("Core module initialized");
-}
\ No newline at end of file
+}
@@ -0,0 +1,232 @@
+//! Content licenses (CONTENT_LICENSE.md).
+//!
+//! Authors pick any license for their posts; the manifest records it as an
+//! SPDX expression. This module parses the expression and answers the
+//! questions clients ask before quoting or remixing a post.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use spdx::{Expression, LicenseItem, LicenseReq};
+use std::fmt;
+
+/// Recommended default license for new posts.
+pub const DEFAULT_LICENSE: &str = "CC-BY-4.0";
+
+/// A parsed SPDX license expression, e.g. `CC-BY-4.0` or
+/// `CC-BY-SA-4.0 OR LicenseRef-proprietary`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentLicense(Expression);
+
+/// What a single license permits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Terms {
+    attribution: bool,
+    derivatives: bool,
+    share_alike: bool,
+    commercial: bool,
+}
+
+/// A license expression that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseError {
+    InvalidExpression(String),
+}
+
+impl fmt::Display for LicenseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LicenseError::InvalidExpression(e) => write!(f, "Invalid license expression: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LicenseError {}
+
+/// A reason a derivative (reply, quote or remix) may conflict with its parent's license.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseWarning {
+    /// A license could not be parsed, so compatibility is unknown.
+    Unrecognized(String),
+    /// The parent does not allow derivatives.
+    NoDerivatives,
+    /// The parent requires derivatives under one of these licenses.
+    ShareAlike(Vec<String>),
+    /// The parent forbids commercial use but the derivative allows it.
+    NonCommercial,
+}
+
+impl fmt::Display for LicenseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LicenseWarning::Unrecognized(license) => write!(f, "Unrecognized license: {}", license),
+            LicenseWarning::NoDerivatives => write!(f, "The original does not allow derivatives"),
+            LicenseWarning::ShareAlike(licenses) => {
+                write!(f, "The original requires derivatives under {}", licenses.join(" or "))
+            }
+            LicenseWarning::NonCommercial => {
+                write!(f, "The original forbids commercial use, but this license allows it")
+            }
+        }
+    }
+}
+
+impl ContentLicense {
+    pub fn parse(expression: &str) -> Result<Self, LicenseError> {
+        Expression::parse(expression)
+            .map(ContentLicense)
+            .map_err(|e| LicenseError::InvalidExpression(e.to_string()))
+    }
+
+    /// True if at least one licensing option allows derivatives.
+    pub fn allows_derivatives(&self) -> bool {
+        self.0.evaluate(|req| terms(req).derivatives)
+    }
+
+    /// True if every licensing option requires attribution.
+    pub fn requires_attribution(&self) -> bool {
+        !self.0.evaluate(|req| !terms(req).attribution)
+    }
+
+    /// True if every licensing option requires derivatives to keep the license.
+    pub fn requires_share_alike(&self) -> bool {
+        !self.0.evaluate(|req| !terms(req).share_alike)
+    }
+
+    /// True if at least one licensing option allows commercial use.
+    pub fn allows_commercial_use(&self) -> bool {
+        self.0.evaluate(|req| terms(req).commercial)
+    }
+
+    /// Warnings for publishing `derivative` as a reply, quote or remix of content
+    /// under this license. Advisory only: clients show them, nothing is blocked.
+    pub fn derivative_warnings(&self, derivative: &ContentLicense) -> Vec<LicenseWarning> {
+        let mut warnings = Vec::new();
+
+        if !self.allows_derivatives() {
+            warnings.push(LicenseWarning::NoDerivatives);
+        }
+
+        if self.requires_share_alike() {
+            let required: Vec<&LicenseItem> = self
+                .0
+                .requirements()
+                .filter(|r| terms(&r.req).share_alike)
+                .map(|r| &r.req.license)
+                .collect();
+            if !derivative.0.evaluate(|req| required.contains(&&req.license)) {
+                warnings.push(LicenseWarning::ShareAlike(required.iter().map(|l| l.to_string()).collect()));
+            }
+        }
+
+        if !self.allows_commercial_use() && derivative.allows_commercial_use() {
+            warnings.push(LicenseWarning::NonCommercial);
+        }
+
+        warnings
+    }
+}
+
+impl Default for ContentLicense {
+    fn default() -> Self {
+        ContentLicense::parse(DEFAULT_LICENSE).expect("default license is a valid SPDX id")
+    }
+}
+
+impl fmt::Display for ContentLicense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for ContentLicense {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.0.as_ref())
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentLicense {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        ContentLicense::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Terms of one license in an expression.
+///
+/// Creative Commons licenses are read from their name (BY, SA, NC, ND).
+/// Other free/open licenses allow everything with attribution, copyleft ones
+/// with share-alike. `LicenseRef-*` and anything else is all rights reserved.
+fn terms(req: &LicenseReq) -> Terms {
+    let reserved = Terms { attribution: true, derivatives: false, share_alike: false, commercial: false };
+
+    let Some(id) = req.license.id() else {
+        return reserved;
+    };
+
+    if id.name.starts_with("CC0-") {
+        return Terms { attribution: false, derivatives: true, share_alike: false, commercial: true };
+    }
+    if let Some(elements) = id.name.strip_prefix("CC-BY") {
+        return Terms {
+            attribution: true,
+            derivatives: !elements.contains("-ND"),
+            share_alike: elements.contains("-SA"),
+            commercial: !elements.contains("-NC"),
+        };
+    }
+    if id.is_osi_approved() || id.is_fsf_free_libre() {
+        return Terms { attribution: true, derivatives: true, share_alike: id.is_copyleft(), commercial: true };
+    }
+    reserved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_attributes() {
+        let by = ContentLicense::default();
+        assert!(by.allows_derivatives() && by.requires_attribution() && by.allows_commercial_use());
+
+        let cc0 = ContentLicense::parse("CC0-1.0").unwrap();
+        assert!(!cc0.requires_attribution());
+
+        let proprietary = ContentLicense::parse("LicenseRef-proprietary").unwrap();
+        assert!(!proprietary.allows_derivatives());
+
+        // OR lets the licensee pick; AND binds them to both
+        let either = ContentLicense::parse("CC-BY-ND-4.0 OR CC0-1.0").unwrap();
+        assert!(either.allows_derivatives() && !either.requires_attribution());
+        let both = ContentLicense::parse("CC-BY-ND-4.0 AND CC0-1.0").unwrap();
+        assert!(!both.allows_derivatives());
+
+        assert!(ContentLicense::parse("CC-BY-4.0 OR").is_err());
+        assert!(ContentLicense::parse("Not-A-License").is_err());
+    }
+
+    #[test]
+    fn test_derivative_warnings() {
+        let parse = |s| ContentLicense::parse(s).unwrap();
+
+        assert!(parse("CC-BY-4.0").derivative_warnings(&parse("CC0-1.0")).is_empty());
+        assert_eq!(
+            parse("CC-BY-ND-4.0").derivative_warnings(&parse("CC-BY-4.0")),
+            vec![LicenseWarning::NoDerivatives]
+        );
+        assert_eq!(
+            parse("CC-BY-SA-4.0").derivative_warnings(&parse("CC-BY-4.0")),
+            vec![LicenseWarning::ShareAlike(vec!["CC-BY-SA-4.0".into()])]
+        );
+        assert!(parse("CC-BY-SA-4.0").derivative_warnings(&parse("CC-BY-SA-4.0")).is_empty());
+        assert_eq!(
+            parse("CC-BY-NC-4.0").derivative_warnings(&parse("CC-BY-4.0")),
+            vec![LicenseWarning::NonCommercial]
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Technical content limits (TECHNICAL_SPEC.md, PAYLOAD_SPECIFICATION.md).
+//!
+//! Every post belongs to a size class with its own byte cap, chunk cap and
+//! accepted codecs. The values live in a versioned [`ProtocolLimits`] that
+//! governance can replace; this module builds without `std` so the runtime
+//! checks registrations against exactly the values clients use.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::fmt;
+use serde::{Deserialize, Serialize};
+use crate::content_type::ContentType;
+
+/// Chunk size fixed by PAYLOAD_SPECIFICATION.md.
+pub const CHUNK_SIZE: u32 = 256 * 1024;
+
+const KIB: u64 = 1024;
+const MIB: u64 = 1024 * KIB;
+
+/// Size class declared in a manifest.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum SizeClass {
+    /// Short text and small blobs.
+    Tiny = 0,
+    Image = 1,
+    ShortVideo = 2,
+    LongVideo = 3,
+}
+
+impl SizeClass {
+    /// Every class, smallest first.
+    pub const ALL: [SizeClass; 4] = [
+        SizeClass::Tiny,
+        SizeClass::Image,
+        SizeClass::ShortVideo,
+        SizeClass::LongVideo,
+    ];
+
+    /// Look up a class by its wire identifier.
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|class| *class as u8 == id)
+    }
+}
+
+/// Limits for one size class.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct ClassLimits {
+    /// Maximum stored (encrypted) size in bytes.
+    pub max_bytes: u64,
+    /// Maximum number of chunks.
+    pub max_chunks: u32,
+    /// Content types accepted in this class.
+    pub codecs: Vec<ContentType>,
+}
+
+/// A versioned set of technical limits.
+///
+/// Changing any value is a governance decision and must bump `version`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct ProtocolLimits {
+    pub version: u32,
+    /// Chunk size in bytes; the last chunk may be shorter.
+    pub chunk_size: u32,
+    pub tiny: ClassLimits,
+    pub image: ClassLimits,
+    pub short_video: ClassLimits,
+    pub long_video: ClassLimits,
+}
+
+impl ProtocolLimits {
+    /// The initial limits.
+    pub fn v1() -> Self {
+        let chunks = |bytes: u64| bytes.div_ceil(CHUNK_SIZE as u64) as u32;
+        Self {
+            version: 1,
+            chunk_size: CHUNK_SIZE,
+            tiny: ClassLimits {
+                max_bytes: 64 * KIB,
+                max_chunks: chunks(64 * KIB),
+                codecs: vec![ContentType::Text, ContentType::Blob],
+            },
+            image: ClassLimits {
+                max_bytes: 16 * MIB,
+                max_chunks: chunks(16 * MIB),
+                codecs: vec![ContentType::WebP, ContentType::Avif],
+            },
+            short_video: ClassLimits {
+                max_bytes: 128 * MIB,
+                max_chunks: chunks(128 * MIB),
+                codecs: vec![ContentType::Av1],
+            },
+            long_video: ClassLimits {
+                max_bytes: 2048 * MIB,
+                max_chunks: chunks(2048 * MIB),
+                codecs: vec![ContentType::Av1],
+            },
+        }
+    }
+
+    pub fn class(&self, class: SizeClass) -> &ClassLimits {
+        match class {
+            SizeClass::Tiny => &self.tiny,
+            SizeClass::Image => &self.image,
+            SizeClass::ShortVideo => &self.short_video,
+            SizeClass::LongVideo => &self.long_video,
+        }
+    }
+
+    /// Number of chunks needed to store `size` bytes (at least one).
+    pub fn chunk_count(&self, size: u64) -> u64 {
+        size.div_ceil(self.chunk_size.max(1) as u64).max(1)
+    }
+
+    /// Check content of `size` stored bytes against its declared class.
+    pub fn check(&self, class: SizeClass, content_type: ContentType, size: u64) -> Result<(), LimitError> {
+        let limits = self.class(class);
+        if !limits.codecs.contains(&content_type) {
+            return Err(LimitError::CodecNotAllowed { class, content_type });
+        }
+        if size > limits.max_bytes {
+            return Err(LimitError::TooLarge { class, size, max: limits.max_bytes });
+        }
+        let chunks = self.chunk_count(size);
+        if chunks > limits.max_chunks as u64 {
+            return Err(LimitError::TooManyChunks { class, chunks, max: limits.max_chunks });
+        }
+        Ok(())
+    }
+
+    /// The smallest class that accepts the content.
+    pub fn classify(&self, content_type: ContentType, size: u64) -> Result<SizeClass, LimitError> {
+        SizeClass::ALL
+            .into_iter()
+            .find(|class| self.check(*class, content_type, size).is_ok())
+            .ok_or(LimitError::NoClassFits { content_type, size })
+    }
+}
+
+impl Default for ProtocolLimits {
+    fn default() -> Self {
+        Self::v1()
+    }
+}
+
+/// Reasons content is outside the technical limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum LimitError {
+    CodecNotAllowed { class: SizeClass, content_type: ContentType },
+    TooLarge { class: SizeClass, size: u64, max: u64 },
+    TooManyChunks { class: SizeClass, chunks: u64, max: u32 },
+    NoClassFits { content_type: ContentType, size: u64 },
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitError::CodecNotAllowed { class, content_type } => {
+                write!(f, "{:?} is not accepted in size class {:?}", content_type, class)
+            }
+            LimitError::TooLarge { class, size, max } => {
+                write!(f, "{} bytes exceeds the {:?} cap of {} bytes", size, class, max)
+            }
+            LimitError::TooManyChunks { class, chunks, max } => {
+                write!(f, "{} chunks exceeds the {:?} cap of {} chunks", chunks, class, max)
+            }
+            LimitError::NoClassFits { content_type, size } => {
+                write!(f, "No size class accepts {} bytes of {:?}", size, content_type)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LimitError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_reports_structured_errors() {
+        let limits = ProtocolLimits::v1();
+        assert!(limits.check(SizeClass::Image, ContentType::WebP, MIB).is_ok());
+        assert_eq!(
+            limits.check(SizeClass::Image, ContentType::Av1, MIB),
+            Err(LimitError::CodecNotAllowed { class: SizeClass::Image, content_type: ContentType::Av1 })
+        );
+        assert_eq!(
+            limits.check(SizeClass::Tiny, ContentType::Text, 64 * KIB + 1),
+            Err(LimitError::TooLarge { class: SizeClass::Tiny, size: 64 * KIB + 1, max: 64 * KIB })
+        );
+    }
+
+    #[test]
+    fn test_classify_picks_smallest_class() {
+        let limits = ProtocolLimits::v1();
+        assert_eq!(limits.classify(ContentType::Av1, 10 * MIB), Ok(SizeClass::ShortVideo));
+        assert_eq!(limits.classify(ContentType::Av1, 512 * MIB), Ok(SizeClass::LongVideo));
+        assert!(limits.classify(ContentType::Text, MIB).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Content manifest, as defined in CRYPTO_PROTOCOL_SPEC.md §6.
+
+use crate::content_type::ContentType;
+use crate::flags::{CouncilFlags, FlagError, GovFlags};
+use crate::license::{ContentLicense, LicenseError, LicenseWarning};
+use crate::limits::{LimitError, ProtocolLimits, SizeClass};
+use crate::region::RegionSet;
+use crypto::signatures::UserSecret;
+use crypto::threshold::CouncilKeySet;
+use crypto::wire::{tag, WireCodec, WireReader, WireWriter};
+use crypto::{CipherSuite, ContentHash, CryptoError, IdentityKey};
+use serde::{Deserialize, Serialize};
+
+/// Current manifest format version.
+pub const MANIFEST_VERSION: u8 = 1;
+
+/// Domain separator for manifest signatures.
+const SIGNING_CONTEXT: &[u8] = b"share-manifest-v1";
+
+/// Public metadata describing one encrypted post.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    /// Format version.
+    pub v: u8,
+    /// Author's identity key.
+    pub auth_id: IdentityKey,
+    /// Author's Ed25519 signature over this manifest.
+    #[serde(with = "serde_bytes")]
+    pub sig: [u8; 64],
+    /// BLAKE3 hash of the ciphertext.
+    pub content_hash: ContentHash,
+    /// Ciphertext size in bytes.
+    pub size: u64,
+    /// Declared type and codec of the payload.
+    pub content_type: ContentType,
+    /// Size class the post is registered under.
+    pub size_class: SizeClass,
+    /// Cipher suite the payload was encrypted under. Manifests written before
+    /// suite IDs existed carry the algorithm name in `algo` instead.
+    #[serde(alias = "algo")]
+    pub suite: CipherSuite,
+    /// Content license as an SPDX expression (see `license()`).
+    pub c_license: String,
+    /// Self-applied content warnings. Council bits here are ignored.
+    pub gov_flags: GovFlags,
+    /// Manifest hash of the post this replies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_reply_to: Option<ContentHash>,
+    /// Manifest hashes of quoted posts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quotes: Vec<ContentHash>,
+    /// Manifest hash of the first post in the conversation. Set on every reply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_root: Option<ContentHash>,
+    /// Regions where the author does not make this post available.
+    #[serde(default, skip_serializing_if = "RegionSet::is_empty")]
+    pub restricted_regions: RegionSet,
+}
+
+impl Manifest {
+    /// Sign as the holder of `secret`, which becomes the author.
+    pub fn sign(&mut self, secret: &UserSecret) -> Result<(), CryptoError> {
+        self.auth_id = secret.public_key();
+        self.sig = secret.sign(&self.signing_bytes()?);
+        Ok(())
+    }
+
+    /// Check the author's signature.
+    pub fn verify(&self) -> Result<(), CryptoError> {
+        self.auth_id.verify(&self.signing_bytes()?, &self.sig)
+    }
+
+    /// BLAKE3 hash of the wire encoding, signature included. Other records
+    /// (summaries, updates, replies) refer to a manifest by this hash.
+    pub fn hash(&self) -> ContentHash {
+        ContentHash::hash(&self.to_wire().expect("manifest fields fit in u32 length prefixes"))
+    }
+
+    fn signing_bytes(&self) -> Result<Vec<u8>, CryptoError> {
+        let mut writer = WireWriter::default();
+        writer.raw(SIGNING_CONTEXT);
+        self.encode_unsigned(&mut writer)?;
+        Ok(writer.into_bytes())
+    }
+
+    fn encode_unsigned(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.u8(self.v);
+        writer.raw(&self.auth_id.as_bytes());
+        writer.raw(self.content_hash.as_bytes());
+        writer.u64(self.size);
+        writer.u8(self.content_type as u8);
+        writer.u8(self.size_class as u8);
+        writer.u16(self.suite.id());
+        writer.bytes(self.c_license.as_bytes())?;
+        writer.u8(self.gov_flags.bits());
+        write_optional_hash(writer, self.in_reply_to.as_ref());
+        let quotes: Vec<u8> = self.quotes.iter().flat_map(|h| *h.as_bytes()).collect();
+        writer.bytes(&quotes)?;
+        write_optional_hash(writer, self.thread_root.as_ref());
+        writer.bytes(self.restricted_regions.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    /// Check the declared type and size against the limits of the declared class.
+    pub fn check_limits(&self, limits: &ProtocolLimits) -> Result<(), LimitError> {
+        limits.check(self.size_class, self.content_type, self.size)
+    }
+
+    /// Fails if the author set council-only flags.
+    pub fn check_flags(&self) -> Result<(), FlagError> {
+        self.gov_flags.check_self_applied()
+    }
+
+    /// The author's flags plus those of the latest valid council statement
+    /// for this content. Council bits in the manifest itself never count.
+    pub fn effective_flags(&self, statements: &[CouncilFlags], council: &CouncilKeySet) -> GovFlags {
+        let council_flags = CouncilFlags::latest(statements, &self.content_hash, council)
+            .map(|s| s.flags)
+            .unwrap_or_default();
+        self.gov_flags.self_applied() | council_flags
+    }
+
+    /// Parse `c_license`. The field stays a string so manifests with an
+    /// unrecognized license still deserialize.
+    pub fn license(&self) -> Result<ContentLicense, LicenseError> {
+        ContentLicense::parse(&self.c_license)
+    }
+
+    /// License warnings for publishing this manifest as a reply or remix of `parent`.
+    pub fn license_warnings(&self, parent: &Manifest) -> Vec<LicenseWarning> {
+        match (parent.license(), self.license()) {
+            (Ok(parent_license), Ok(license)) => parent_license.derivative_warnings(&license),
+            (Err(_), _) => vec![LicenseWarning::Unrecognized(parent.c_license.clone())],
+            (_, Err(_)) => vec![LicenseWarning::Unrecognized(self.c_license.clone())],
+        }
+    }
+}
+
+impl WireCodec for Manifest {
+    const TAG: u8 = tag::MANIFEST;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        self.encode_unsigned(writer)?;
+        writer.raw(&self.sig);
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        Ok(Manifest {
+            v: reader.u8()?,
+            auth_id: IdentityKey::from_bytes(&reader.array()?)?,
+            content_hash: ContentHash::from_bytes(reader.array()?),
+            size: reader.u64()?,
+            content_type: ContentType::from_id(reader.u8()?)
+                .ok_or_else(|| CryptoError::DecodingError("Unknown content type".into()))?,
+            size_class: SizeClass::from_id(reader.u8()?)
+                .ok_or_else(|| CryptoError::DecodingError("Unknown size class".into()))?,
+            suite: CipherSuite::from_id(reader.u16()?)?,
+            c_license: String::from_utf8(reader.bytes()?)
+                .map_err(|_| CryptoError::DecodingError("Invalid UTF-8".into()))?,
+            gov_flags: GovFlags::from_bits_retain(reader.u8()?),
+            in_reply_to: read_optional_hash(reader)?,
+            quotes: read_hashes(reader)?,
+            thread_root: read_optional_hash(reader)?,
+            restricted_regions: read_regions(reader)?,
+            sig: reader.array()?,
+        })
+    }
+}
+
+fn write_optional_hash(writer: &mut WireWriter, hash: Option<&ContentHash>) {
+    match hash {
+        None => writer.u8(0),
+        Some(hash) => {
+            writer.u8(1);
+            writer.raw(hash.as_bytes());
+        }
+    }
+}
+
+fn read_optional_hash(reader: &mut WireReader<'_>) -> Result<Option<ContentHash>, CryptoError> {
+    match reader.u8()? {
+        0 => Ok(None),
+        1 => Ok(Some(ContentHash::from_bytes(reader.array()?))),
+        flag => Err(CryptoError::DecodingError(format!("Invalid option flag {}", flag))),
+    }
+}
+
+fn read_regions(reader: &mut WireReader<'_>) -> Result<RegionSet, CryptoError> {
+    let list = String::from_utf8(reader.bytes()?).map_err(|_| CryptoError::DecodingError("Invalid UTF-8".into()))?;
+    let regions = RegionSet::parse(&list).map_err(|e| CryptoError::DecodingError(e.to_string()))?;
+    // Only the canonical form is accepted, so each manifest has one encoding
+    if regions.to_string() != list {
+        return Err(CryptoError::DecodingError("Region list is not in canonical form".into()));
+    }
+    Ok(regions)
+}
+
+fn read_hashes(reader: &mut WireReader<'_>) -> Result<Vec<ContentHash>, CryptoError> {
+    let bytes = reader.bytes()?;
+    if !bytes.len().is_multiple_of(32) {
+        return Err(CryptoError::DecodingError("Hash list length is not a multiple of 32".into()));
+    }
+    Ok(bytes
+        .chunks_exact(32)
+        .map(|chunk| ContentHash::from_bytes(chunk.try_into().expect("32-byte chunk")))
+        .collect())
+}
+
+/// Test fixture: a text manifest for `body` under the default license,
+/// adjusted by `edit` and then signed by `secret`.
+#[cfg(test)]
+pub(crate) fn test_manifest(secret: &UserSecret, body: &[u8], edit: impl FnOnce(&mut Manifest)) -> Manifest {
+    let mut manifest = Manifest {
+        v: MANIFEST_VERSION,
+        auth_id: secret.public_key(),
+        sig: [0u8; 64],
+        content_hash: ContentHash::hash(body),
+        size: body.len() as u64,
+        content_type: ContentType::Text,
+        size_class: SizeClass::Tiny,
+        suite: CipherSuite::CURRENT,
+        c_license: "CC-BY-4.0".into(),
+        gov_flags: GovFlags::empty(),
+        in_reply_to: None,
+        quotes: Vec::new(),
+        thread_root: None,
+        restricted_regions: RegionSet::new(),
+    };
+    edit(&mut manifest);
+    manifest.sign(secret).expect("test manifest fits the wire format");
+    manifest
+}
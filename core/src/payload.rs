@@ -1,9 +1,271 @@
+//! Typed payloads with mandatory compression (PAYLOAD_SPECIFICATION.md).
+//!
+//! Text and generic blobs are zstd-compressed here at a fixed level; media must
+//! arrive already compressed in an accepted codec and is only validated.
+
+use crypto::encryption::{encrypt, SymmetricKey, NONCE_SIZE};
+use crypto::Ciphertext;
+use crate::limits::{LimitError, ProtocolLimits, SizeClass};
+use std::fmt;
+
+pub use crate::content_type::ContentType;
+
+/// zstd level used for all text and blob payloads. Fixed so that identical
+/// input yields identical output (and therefore identical content hashes).
+pub const ZSTD_LEVEL: i32 = 19;
+
+/// Bytes added by encryption: the nonce plus the Poly1305 tag.
+pub const AEAD_OVERHEAD: u64 = NONCE_SIZE as u64 + 16;
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Reasons a payload is refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadError {
+    CompressionFailed(String),
+    /// Not a single zstd frame with a declared content size.
+    NotCompressed,
+    /// The bytes do not match the declared type.
+    CodecMismatch(ContentType),
+    InvalidUtf8,
+    DecompressionFailed(String),
+    /// The frame declares more content than the size class allows.
+    TooLarge { declared: u64, max: u64 },
+    EncryptionFailed(String),
+}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadError::CompressionFailed(e) => write!(f, "Compression failed: {}", e),
+            PayloadError::NotCompressed => {
+                write!(f, "Payload is not a single zstd frame with a declared content size")
+            }
+            PayloadError::CodecMismatch(t) => write!(f, "Payload bytes do not match declared type {:?}", t),
+            PayloadError::InvalidUtf8 => write!(f, "Text payload is not valid UTF-8"),
+            PayloadError::DecompressionFailed(e) => write!(f, "Decompression failed: {}", e),
+            PayloadError::TooLarge { declared, max } => {
+                write!(f, "Payload declares {} bytes, more than the limit of {}", declared, max)
+            }
+            PayloadError::EncryptionFailed(e) => write!(f, "Encryption failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PayloadError {}
+
+/// A validated, compressed payload ready for encryption.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Payload {
+    pub content_type: ContentType,
     pub data: Vec<u8>,
 }
 
 impl Payload {
-    pub fn new(data: Vec<u8>) -> Self {
-        Self { data }
+    /// Wrap already-encoded bytes, rejecting uncompressed or mislabeled data.
+    pub fn new(content_type: ContentType, data: Vec<u8>) -> Result<Self, PayloadError> {
+        let payload = Self { content_type, data };
+        payload.validate()?;
+        Ok(payload)
+    }
+
+    /// Compress UTF-8 text.
+    pub fn text(text: &str) -> Result<Self, PayloadError> {
+        Ok(Self {
+            content_type: ContentType::Text,
+            data: compress(text.as_bytes())?,
+        })
+    }
+
+    /// Compress an opaque blob.
+    pub fn blob(bytes: &[u8]) -> Result<Self, PayloadError> {
+        Ok(Self {
+            content_type: ContentType::Blob,
+            data: compress(bytes)?,
+        })
+    }
+
+    /// Check that the bytes match the declared type.
+    ///
+    /// Clients call this on every payload before encrypting or seeding it.
+    pub fn validate(&self) -> Result<(), PayloadError> {
+        let valid = match self.content_type {
+            ContentType::Text | ContentType::Blob => {
+                zstd_content_size(&self.data)?;
+                true
+            }
+            ContentType::WebP => is_webp(&self.data),
+            ContentType::Avif => has_isobmff_brand(&self.data, &[b"avif", b"avis"]),
+            ContentType::Av1 => has_isobmff_brand(&self.data, &[b"av01"]),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(PayloadError::CodecMismatch(self.content_type))
+        }
+    }
+
+    /// Check the payload, once encrypted, against the limits of `class`.
+    pub fn check_limits(&self, limits: &ProtocolLimits, class: SizeClass) -> Result<(), LimitError> {
+        limits.check(class, self.content_type, self.encrypted_len())
+    }
+
+    /// Size of the ciphertext this payload encrypts to.
+    pub fn encrypted_len(&self) -> u64 {
+        self.data.len() as u64 + AEAD_OVERHEAD
+    }
+
+    /// Recover the original bytes of a text or blob payload.
+    ///
+    /// Media payloads are returned as-is. The frame's declared content size
+    /// is checked against the byte limit of `class` before anything is
+    /// allocated, so a forged frame cannot expand beyond it.
+    pub fn decompress(&self, limits: &ProtocolLimits, class: SizeClass) -> Result<Vec<u8>, PayloadError> {
+        if !self.content_type.is_zstd() {
+            return Ok(self.data.clone());
+        }
+
+        let size = zstd_content_size(&self.data)?;
+        let max = limits.class(class).max_bytes;
+        if size > max {
+            return Err(PayloadError::TooLarge { declared: size, max });
+        }
+        let capacity = usize::try_from(size)
+            .map_err(|_| PayloadError::DecompressionFailed("content size exceeds address space".into()))?;
+        let bytes = zstd::bulk::decompress(&self.data, capacity)
+            .map_err(|e| PayloadError::DecompressionFailed(e.to_string()))?;
+
+        if self.content_type == ContentType::Text && std::str::from_utf8(&bytes).is_err() {
+            return Err(PayloadError::InvalidUtf8);
+        }
+        Ok(bytes)
+    }
+
+    /// Validate, then encrypt under a fresh DEK (CRYPTO_PROTOCOL_SPEC.md §4.1).
+    pub fn encrypt(&self, key: &SymmetricKey, aad: &[u8]) -> Result<Ciphertext, PayloadError> {
+        self.validate()?;
+        encrypt(&self.data, key, aad).map_err(|e| PayloadError::EncryptionFailed(e.to_string()))
+    }
+}
+
+fn compress(bytes: &[u8]) -> Result<Vec<u8>, PayloadError> {
+    // bulk::compress knows the input length up front, so the frame header
+    // always records the content size.
+    zstd::bulk::compress(bytes, ZSTD_LEVEL).map_err(|e| PayloadError::CompressionFailed(e.to_string()))
+}
+
+/// Content size of a payload that must be exactly one zstd frame.
+fn zstd_content_size(data: &[u8]) -> Result<u64, PayloadError> {
+    if !data.starts_with(&ZSTD_MAGIC) {
+        return Err(PayloadError::NotCompressed);
+    }
+    match zstd::zstd_safe::find_frame_compressed_size(data) {
+        Ok(len) if len == data.len() => {}
+        _ => return Err(PayloadError::NotCompressed),
+    }
+    match zstd::zstd_safe::get_frame_content_size(data) {
+        Ok(Some(size)) => Ok(size),
+        _ => Err(PayloadError::NotCompressed),
+    }
+}
+
+/// RIFF/WEBP header with a known first chunk and a RIFF size matching the data.
+fn is_webp(data: &[u8]) -> bool {
+    if data.len() < 16 || &data[0..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return false;
+    }
+    let riff_size = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
+    let chunk = &data[12..16];
+    riff_size + 8 == data.len() && (chunk == b"VP8 " || chunk == b"VP8L" || chunk == b"VP8X")
+}
+
+/// ISO BMFF file whose leading `ftyp` box lists one of `brands`.
+fn has_isobmff_brand(data: &[u8], brands: &[&[u8; 4]]) -> bool {
+    if data.len() < 16 || &data[4..8] != b"ftyp" {
+        return false;
+    }
+    let box_size = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    if box_size < 16 || box_size > data.len() || !box_size.is_multiple_of(4) {
+        return false;
+    }
+
+    // major_brand (8..12), minor_version (12..16), compatible_brands (16..box_size)
+    std::iter::once(&data[8..12])
+        .chain(data[16..box_size].chunks(4))
+        .any(|brand| brands.iter().any(|b| brand == &b[..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_roundtrip_is_deterministic() {
+        let a = Payload::text("Hello, decentralized world!").unwrap();
+        let b = Payload::text("Hello, decentralized world!").unwrap();
+        assert_eq!(a.data, b.data);
+        assert!(a.validate().is_ok());
+        assert_eq!(
+            a.decompress(&ProtocolLimits::v1(), SizeClass::Tiny).unwrap(),
+            b"Hello, decentralized world!"
+        );
+    }
+
+    #[test]
+    fn test_decompress_rejects_oversized_declaration() {
+        // Single-segment frame declaring 2^60 bytes, followed by one empty raw block.
+        let mut frame = ZSTD_MAGIC.to_vec();
+        frame.push(0xE0);
+        frame.extend_from_slice(&(1u64 << 60).to_le_bytes());
+        frame.extend_from_slice(&[0x01, 0x00, 0x00]);
+        let forged = Payload { content_type: ContentType::Text, data: frame };
+
+        let limits = ProtocolLimits::v1();
+        let max = limits.class(SizeClass::Tiny).max_bytes;
+        assert_eq!(
+            forged.decompress(&limits, SizeClass::Tiny),
+            Err(PayloadError::TooLarge { declared: 1 << 60, max })
+        );
+    }
+
+    #[test]
+    fn test_rejects_uncompressed_and_mislabeled() {
+        assert_eq!(
+            Payload::new(ContentType::Text, b"plain text".to_vec()),
+            Err(PayloadError::NotCompressed)
+        );
+
+        let blob = Payload::blob(b"not an image").unwrap();
+        assert_eq!(
+            Payload::new(ContentType::WebP, blob.data),
+            Err(PayloadError::CodecMismatch(ContentType::WebP))
+        );
+    }
+
+    #[test]
+    fn test_accepts_media_headers() {
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8L".to_vec();
+        webp.extend_from_slice(&[0u8; 8]);
+        let riff_size = (webp.len() - 8) as u32;
+        webp[4..8].copy_from_slice(&riff_size.to_le_bytes());
+        assert!(Payload::new(ContentType::WebP, webp).is_ok());
+
+        let mut mp4 = vec![0, 0, 0, 24];
+        mp4.extend_from_slice(b"ftypisom\0\0\x02\0isomav01");
+        mp4.extend_from_slice(&[0u8; 16]);
+        assert!(Payload::new(ContentType::Av1, mp4.clone()).is_ok());
+        assert!(Payload::new(ContentType::Avif, mp4).is_err());
+    }
+
+    #[test]
+    fn test_check_limits_counts_encryption_overhead() {
+        let limits = ProtocolLimits::v1();
+        let payload = Payload::text("short note").unwrap();
+        assert!(payload.check_limits(&limits, SizeClass::Tiny).is_ok());
+
+        let key = SymmetricKey::generate();
+        let ciphertext = payload.encrypt(&key, b"").unwrap();
+        assert_eq!(payload.encrypted_len(), (ciphertext.nonce.len() + ciphertext.data.len()) as u64);
     }
 }
@@ -0,0 +1,283 @@
+//! Regional restrictions (TECHNICAL_SPEC.md, GOVERNANCE_CHARTER.md).
+//!
+//! Authors can mark content unavailable in some regions, and councils adopt
+//! supplemental guidelines that apply only in some regions. Clients evaluate
+//! both against the user's configured region before syncing, so they never
+//! download content they will not be allowed to show.
+
+use crate::flags::GovFlags;
+use crate::manifest::Manifest;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+const EU_MEMBERS: &[&str] = &[
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT", "LT",
+    "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+const EEA_EXTRA: &[&str] = &["IS", "LI", "NO"];
+
+/// An ISO 3166-1 alpha-2 country code, e.g. `DE`.
+///
+/// Only the format (two ASCII letters, stored upper-case) is checked, so newly
+/// assigned codes work without a client update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CountryCode([u8; 2]);
+
+impl CountryCode {
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("ASCII letters")
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = RegionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                Ok(CountryCode([a.to_ascii_uppercase(), b.to_ascii_uppercase()]))
+            }
+            _ => Err(RegionError::InvalidRegion(s.to_string())),
+        }
+    }
+}
+
+/// A named group of countries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MacroRegion {
+    /// European Union member states.
+    Eu,
+    /// European Economic Area: the EU plus Iceland, Liechtenstein and Norway.
+    Eea,
+}
+
+impl MacroRegion {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MacroRegion::Eu => "EU",
+            MacroRegion::Eea => "EEA",
+        }
+    }
+
+    pub fn contains(&self, country: CountryCode) -> bool {
+        let code = country.as_str();
+        match self {
+            MacroRegion::Eu => EU_MEMBERS.contains(&code),
+            MacroRegion::Eea => EU_MEMBERS.contains(&code) || EEA_EXTRA.contains(&code),
+        }
+    }
+}
+
+/// A set of countries and macro-regions, written as a list such as `["DE", "EEA"]`.
+///
+/// `EU` is parsed as the macro-region (ISO 3166 reserves it for that purpose).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RegionSet {
+    countries: BTreeSet<CountryCode>,
+    macros: BTreeSet<MacroRegion>,
+}
+
+impl RegionSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.countries.is_empty() && self.macros.is_empty()
+    }
+
+    pub fn insert_country(&mut self, country: CountryCode) {
+        self.countries.insert(country);
+    }
+
+    pub fn insert_macro(&mut self, region: MacroRegion) {
+        self.macros.insert(region);
+    }
+
+    /// True if the country is listed directly or through a macro-region.
+    pub fn contains(&self, country: CountryCode) -> bool {
+        self.countries.contains(&country) || self.macros.iter().any(|m| m.contains(country))
+    }
+
+    /// Parse a comma-separated list, e.g. `DE,FR,EEA`.
+    pub fn parse(list: &str) -> Result<Self, RegionError> {
+        let mut set = RegionSet::new();
+        for item in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            set.insert_item(item)?;
+        }
+        Ok(set)
+    }
+
+    fn insert_item(&mut self, item: &str) -> Result<(), RegionError> {
+        match item.to_ascii_uppercase().as_str() {
+            "EU" => self.insert_macro(MacroRegion::Eu),
+            "EEA" => self.insert_macro(MacroRegion::Eea),
+            _ => self.insert_country(item.parse()?),
+        }
+        Ok(())
+    }
+
+    fn items(&self) -> Vec<&str> {
+        self.countries
+            .iter()
+            .map(CountryCode::as_str)
+            .chain(self.macros.iter().map(|m| m.name()))
+            .collect()
+    }
+}
+
+// Canonical form: countries in code order, then macro-regions, comma-separated
+impl fmt::Display for RegionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.items().join(","))
+    }
+}
+
+impl Serialize for RegionSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.items().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RegionSet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let items = Vec::<String>::deserialize(deserializer)?;
+        let mut set = RegionSet::new();
+        for item in &items {
+            set.insert_item(item).map_err(serde::de::Error::custom)?;
+        }
+        Ok(set)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegionError {
+    InvalidRegion(String),
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegionError::InvalidRegion(s) => write!(f, "Not a country code or macro-region: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for RegionError {}
+
+/// What a client may do with a post in the user's region. Ordered from most
+/// to least permissive; when several rules apply the strictest wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionDecision {
+    Allow,
+    /// Show the summary, do not fetch the payload.
+    SummaryOnly,
+    /// Fetch nothing.
+    Deny,
+}
+
+impl RegionDecision {
+    /// Whether the sync engine should download the payload.
+    pub fn allows_download(&self) -> bool {
+        *self == RegionDecision::Allow
+    }
+}
+
+/// A supplemental guideline adopted for specific regions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RegionalGuideline {
+    pub regions: RegionSet,
+    /// The guideline covers content carrying any of these flags.
+    pub flags: GovFlags,
+    /// `SummaryOnly` or `Deny`.
+    pub action: RegionDecision,
+}
+
+/// Decide how a post may be shown to a user in `region`.
+///
+/// `flags` are the post's effective flags (`Manifest::effective_flags`). A user
+/// with no configured region gets `Allow`, since no regional rule can be
+/// matched.
+pub fn evaluate(
+    region: Option<CountryCode>,
+    manifest: &Manifest,
+    flags: GovFlags,
+    guidelines: &[RegionalGuideline],
+) -> RegionDecision {
+    let Some(region) = region else {
+        return RegionDecision::Allow;
+    };
+
+    let restricted = manifest.restricted_regions.contains(region).then_some(RegionDecision::Deny);
+
+    guidelines
+        .iter()
+        .filter(|g| g.regions.contains(region) && flags.intersects(g.flags))
+        .map(|g| g.action)
+        .chain(restricted)
+        .max()
+        .unwrap_or(RegionDecision::Allow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content_type::ContentType;
+    use crate::limits::SizeClass;
+    use crate::manifest::test_manifest;
+    use crypto::signatures::UserSecret;
+
+    #[test]
+    fn test_region_set_parse_and_membership() {
+        let set = RegionSet::parse("ch, EEA").unwrap();
+        assert!(set.contains("CH".parse().unwrap()));
+        assert!(set.contains("NO".parse().unwrap()));
+        assert!(set.contains("DE".parse().unwrap()));
+        assert!(!set.contains("US".parse().unwrap()));
+        assert_eq!(set.to_string(), "CH,EEA");
+
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(serde_json::from_str::<RegionSet>(&json).unwrap(), set);
+        assert!(RegionSet::parse("Germany").is_err());
+    }
+
+    #[test]
+    fn test_strictest_matching_rule_wins() {
+        let secret = UserSecret::generate();
+        let manifest = test_manifest(&secret, b"clip", |m| {
+            m.content_type = ContentType::Av1;
+            m.size_class = SizeClass::ShortVideo;
+            m.gov_flags = GovFlags::VIOLENCE;
+            m.restricted_regions = RegionSet::parse("US").unwrap();
+        });
+
+        let guidelines = [
+            RegionalGuideline {
+                regions: RegionSet::parse("EU").unwrap(),
+                flags: GovFlags::VIOLENCE,
+                action: RegionDecision::SummaryOnly,
+            },
+            RegionalGuideline {
+                regions: RegionSet::parse("DE").unwrap(),
+                flags: GovFlags::VIOLENCE | GovFlags::REGION_RESTRICTED,
+                action: RegionDecision::Deny,
+            },
+        ];
+        let decide = |region: &str| evaluate(Some(region.parse().unwrap()), &manifest, GovFlags::VIOLENCE, &guidelines);
+
+        assert_eq!(decide("FR"), RegionDecision::SummaryOnly);
+        assert_eq!(decide("DE"), RegionDecision::Deny);
+        assert_eq!(decide("JP"), RegionDecision::Allow);
+        assert_eq!(decide("US"), RegionDecision::Deny);
+        assert_eq!(evaluate(None, &manifest, GovFlags::VIOLENCE, &guidelines), RegionDecision::Allow);
+    }
+}
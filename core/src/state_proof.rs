@@ -0,0 +1,349 @@
+//! Storage proofs against a runtime state root.
+//!
+//! Clients read values the chain wrote, such as a community ratification
+//! record (`fork`), from a set of trie nodes that hash up to a state root
+//! their light client has seen finalized. Nodes use the runtime's trie
+//! format: a base-16 Patricia trie without extension nodes, hashed with
+//! BLAKE2b-256, whose values of 33 bytes or more are stored in separate nodes
+//! by hash (state version 1). Nodes with inline values (version 0) are read
+//! too.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use blake2::digest::consts::{U16, U32};
+use blake2::{Blake2b, Digest};
+
+/// Bytes in a node or value hash.
+const HASH_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateProofError {
+    /// A node on the path to the key is not in the proof.
+    MissingNode([u8; 32]),
+    /// A node or value in the proof cannot be decoded.
+    InvalidNode,
+}
+
+impl fmt::Display for StateProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateProofError::MissingNode(hash) => write!(f, "Storage proof lacks node {}", hex(hash)),
+            StateProofError::InvalidNode => write!(f, "Storage proof contains an invalid node"),
+        }
+    }
+}
+
+impl std::error::Error for StateProofError {}
+
+/// Storage key of `key` in a map declared as
+/// `StorageMap<_, Blake2_128Concat, [u8; 32], _>` by `pallet`.
+pub fn map_key(pallet: &str, storage: &str, key: &[u8; 32]) -> Vec<u8> {
+    [&twox_128(pallet.as_bytes())[..], &twox_128(storage.as_bytes()), &blake2_128(key), key].concat()
+}
+
+/// The value stored under `key` in the state with root `state_root`, read
+/// from the trie nodes in `proof`. `Ok(None)` means the proof shows the key
+/// is absent.
+pub fn read(state_root: &[u8; 32], proof: &[Vec<u8>], key: &[u8]) -> Result<Option<Vec<u8>>, StateProofError> {
+    let nodes: HashMap<[u8; 32], &[u8]> = proof.iter().map(|node| (blake2_256(node), node.as_slice())).collect();
+    let lookup = |hash: &[u8; 32]| nodes.get(hash).copied().ok_or(StateProofError::MissingNode(*hash));
+    let key: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0F]).collect();
+
+    let mut data = lookup(state_root)?;
+    let mut at = 0;
+    loop {
+        let node = Node::decode(data)?;
+        let rest = &key[at..];
+        if !rest.starts_with(&node.partial) {
+            return Ok(None);
+        }
+        at += node.partial.len();
+        if at == key.len() {
+            return match node.value {
+                None => Ok(None),
+                Some(Value::Inline(value)) => Ok(Some(value.to_vec())),
+                Some(Value::Hashed(hash)) => Ok(Some(lookup(&hash)?.to_vec())),
+            };
+        }
+        let Some(children) = node.children else {
+            return Ok(None);
+        };
+        data = match children[key[at] as usize] {
+            None => return Ok(None),
+            Some(Child::Inline(child)) => child,
+            Some(Child::Hashed(hash)) => lookup(&hash)?,
+        };
+        at += 1;
+    }
+}
+
+/// Root and proof of a state holding nothing but `value` (at least 33 bytes)
+/// under `key`: a single leaf referring to `value` by hash. For tests of code
+/// that reads storage proofs.
+pub fn single_entry(key: &[u8], value: &[u8]) -> ([u8; 32], Vec<Vec<u8>>) {
+    assert!(value.len() > HASH_LENGTH, "shorter values are stored inline");
+    // Hashed-value leaf header: 0b001 and the nibble count, continued in
+    // further bytes from 31 nibbles on.
+    let nibbles = key.len() * 2;
+    let mut leaf = vec![0b0010_0000 | nibbles.min(31) as u8];
+    if nibbles >= 31 {
+        let mut rest = nibbles - 31;
+        while rest >= 255 {
+            leaf.push(255);
+            rest -= 255;
+        }
+        leaf.push(rest as u8);
+    }
+    leaf.extend_from_slice(key);
+    leaf.extend_from_slice(&blake2_256(value));
+    (blake2_256(&leaf), vec![leaf, value.to_vec()])
+}
+
+enum Value<'a> {
+    Inline(&'a [u8]),
+    Hashed([u8; 32]),
+}
+
+enum Child<'a> {
+    Inline(&'a [u8]),
+    Hashed([u8; 32]),
+}
+
+/// A decoded trie node. Leaves have no `children`.
+struct Node<'a> {
+    /// Key nibbles consumed by this node.
+    partial: Vec<u8>,
+    value: Option<Value<'a>>,
+    children: Option<[Option<Child<'a>>; 16]>,
+}
+
+impl<'a> Node<'a> {
+    fn decode(data: &'a [u8]) -> Result<Self, StateProofError> {
+        let mut input = Input(data);
+        let first = input.byte()?;
+        if first == 0 {
+            return Ok(Node { partial: Vec::new(), value: None, children: None });
+        }
+        // (is a branch, has a value, value is hashed, header bits before the length)
+        let (branch, has_value, hashed, prefix_bits) = match first >> 6 {
+            0b01 => (false, true, false, 2),
+            0b10 => (true, false, false, 2),
+            0b11 => (true, true, false, 2),
+            _ if first >> 5 == 0b001 => (false, true, true, 3),
+            _ if first >> 4 == 0b0001 => (true, true, true, 4),
+            _ => return Err(StateProofError::InvalidNode),
+        };
+
+        let nibbles = input.size(first, prefix_bits)?;
+        let packed = input.take(nibbles.div_ceil(2))?;
+        if nibbles % 2 == 1 && packed[0] >> 4 != 0 {
+            return Err(StateProofError::InvalidNode);
+        }
+        let mut partial: Vec<u8> = packed.iter().flat_map(|b| [b >> 4, b & 0x0F]).collect();
+        partial.drain(..partial.len() - nibbles);
+
+        let bitmap = if branch {
+            let bitmap = u16::from_le_bytes([input.byte()?, input.byte()?]);
+            if bitmap == 0 {
+                return Err(StateProofError::InvalidNode);
+            }
+            Some(bitmap)
+        } else {
+            None
+        };
+        let value = match (has_value, hashed) {
+            (false, _) => None,
+            (true, true) => Some(Value::Hashed(input.hash()?)),
+            (true, false) => {
+                let length = input.compact()?;
+                Some(Value::Inline(input.take(length)?))
+            }
+        };
+        let children = match bitmap {
+            None => None,
+            Some(bitmap) => {
+                let mut children: [Option<Child<'a>>; 16] = Default::default();
+                for (nibble, child) in children.iter_mut().enumerate() {
+                    if bitmap & (1 << nibble) == 0 {
+                        continue;
+                    }
+                    let length = input.compact()?;
+                    let data = input.take(length)?;
+                    *child = Some(match <[u8; HASH_LENGTH]>::try_from(data) {
+                        Ok(hash) => Child::Hashed(hash),
+                        Err(_) => Child::Inline(data),
+                    });
+                }
+                Some(children)
+            }
+        };
+        if !input.0.is_empty() {
+            return Err(StateProofError::InvalidNode);
+        }
+        Ok(Node { partial, value, children })
+    }
+}
+
+struct Input<'a>(&'a [u8]);
+
+impl<'a> Input<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], StateProofError> {
+        if count > self.0.len() {
+            return Err(StateProofError::InvalidNode);
+        }
+        let (taken, rest) = self.0.split_at(count);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8, StateProofError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn hash(&mut self) -> Result<[u8; 32], StateProofError> {
+        Ok(self.take(HASH_LENGTH)?.try_into().expect("took a hash"))
+    }
+
+    /// Nibble count from the low bits of the header byte `first`, continued
+    /// in following bytes while they are all ones.
+    fn size(&mut self, first: u8, prefix_bits: u32) -> Result<usize, StateProofError> {
+        let max = (0xFFu8 >> prefix_bits) as usize;
+        let mut size = (first as usize) & max;
+        if size < max {
+            return Ok(size);
+        }
+        loop {
+            let next = self.byte()? as usize;
+            size += next;
+            if next < 255 {
+                return Ok(size);
+            }
+        }
+    }
+
+    /// A SCALE compact length.
+    fn compact(&mut self) -> Result<usize, StateProofError> {
+        let first = self.byte()?;
+        let value = match first & 0b11 {
+            0 => u32::from(first >> 2),
+            1 => u32::from(u16::from_le_bytes([first, self.byte()?]) >> 2),
+            2 => {
+                let rest = self.take(3)?;
+                u32::from_le_bytes([first, rest[0], rest[1], rest[2]]) >> 2
+            }
+            _ if first >> 2 == 0 => u32::from_le_bytes(self.take(4)?.try_into().expect("took four bytes")),
+            _ => return Err(StateProofError::InvalidNode),
+        };
+        Ok(value as usize)
+    }
+}
+
+fn blake2_256(data: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(data).into()
+}
+
+fn blake2_128(data: &[u8]) -> [u8; 16] {
+    Blake2b::<U16>::digest(data).into()
+}
+
+fn twox_128(data: &[u8]) -> [u8; 16] {
+    use std::hash::Hasher;
+    let mut out = [0u8; 16];
+    for (seed, half) in out.chunks_mut(8).enumerate() {
+        let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+        hasher.write(data);
+        half.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    out
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Generated with `sp-trie` 22 (`LayoutV1<BlakeTwo256>`) for a state with
+    // two `Governance::CommunityRatifications` records, for forks [7; 32] and
+    // [8; 32], next to `Governance::State`, `System::Number` and `:code`.
+    const STATE_ROOT: &str = "7ba81c62bd77055713f3c4ec9ed7418778967c165f465d5b7a764295381ca165";
+    const SHARED: [&str; 3] = [
+        "804c00809ecd7c4e29d2fd7e960f847633bde33914ca0c141650cb1b6e582651cebbfc6b80e2880f4473f3e170135d8da9ccb66b424aaf72ed8c7278b78a58e95962bfadcb808102011068e92f78c7ddf345dc8b9fa2a652c3b51c2167611930158c2f88f3a7",
+        "9f05451d8459cff1bb312fc1144f5d82f9003080a664cb5eccbad61df64dc842cd25620f5c5a0f95c1769a0cbe74feb98c3ccdeb80c2f2a0e170a7a4d7f633b5d3457ebb47cc5fd3dae498a2132d6ee2b2a722d28f",
+        "9f07aa58336fa3bd26b05614ca0cff244e00c080b55081598034a8346be0fe7f8073cb81ac4dec0a6741b05a1ee881716fa59b174c5f039a107f2d8d3854c9aba9b021f43d9c0401",
+    ];
+    const RECORD_LEAF: &str = "3f400dd6249d8a7334cf5ea37d21811e6671070707070707070707070707070707070707070707070707070707070707070716684ddd272dfc18dd40c16a7f57cecca70df0a96c4a066b97646953e7c7691a";
+    const OTHER_LEAF: &str = "3f400731d290cfc58fa7e2a1253e0a1c342508080808080808080808080808080808080808080808080808080808080808080752fefdb31ecb56bf284a6f9c8ec855273c3226a752f33c64f5472263c35224";
+
+    fn unhex(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    fn root() -> [u8; 32] {
+        unhex(STATE_ROOT).try_into().unwrap()
+    }
+
+    fn record() -> Vec<u8> {
+        (0..120).collect()
+    }
+
+    fn proof(leaf: &str, value: Option<Vec<u8>>) -> Vec<Vec<u8>> {
+        SHARED.iter().chain([&leaf]).map(|node| unhex(node)).chain(value).collect()
+    }
+
+    fn key(fork: u8) -> Vec<u8> {
+        map_key("Governance", "CommunityRatifications", &[fork; 32])
+    }
+
+    #[test]
+    fn test_map_key() {
+        assert_eq!(
+            key(7),
+            unhex(
+                "67aa58336fa3bd26b05614ca0cff244ee5451d8459cff1bb312fc1144f5d82f9\
+                 ddd6249d8a7334cf5ea37d21811e66710707070707070707070707070707070707070707070707070707070707070707"
+            )
+        );
+    }
+
+    #[test]
+    fn test_read_present_and_absent() {
+        assert_eq!(read(&root(), &proof(RECORD_LEAF, Some(record())), &key(7)), Ok(Some(record())));
+        // The proof of absence ends at the leaf of the neighbouring fork.
+        assert_eq!(read(&root(), &proof(OTHER_LEAF, None), &key(9)), Ok(None));
+        // That leaf does not prove the record either way.
+        assert!(matches!(read(&root(), &proof(OTHER_LEAF, None), &key(7)), Err(StateProofError::MissingNode(_))));
+    }
+
+    #[test]
+    fn test_read_rejects_altered_proofs() {
+        let value = blake2_256(&record());
+        assert_eq!(read(&root(), &proof(RECORD_LEAF, None), &key(7)), Err(StateProofError::MissingNode(value)));
+
+        let mut forged = record();
+        forged[40] ^= 1;
+        assert_eq!(read(&root(), &proof(RECORD_LEAF, Some(forged)), &key(7)), Err(StateProofError::MissingNode(value)));
+
+        let mut nodes = proof(RECORD_LEAF, Some(record()));
+        nodes[0].push(0);
+        assert!(matches!(read(&root(), &nodes, &key(7)), Err(StateProofError::MissingNode(_))));
+
+        let (other_root, nodes) = single_entry(&key(7), &record());
+        assert!(matches!(read(&root(), &nodes, &key(7)), Err(StateProofError::MissingNode(_))));
+        assert_eq!(read(&other_root, &nodes[..1], &key(7)), Err(StateProofError::MissingNode(value)));
+
+        let garbage = vec![0x01];
+        assert_eq!(read(&blake2_256(&garbage), &[garbage], &key(7)), Err(StateProofError::InvalidNode));
+    }
+
+    #[test]
+    fn test_single_entry_matches_runtime_trie() {
+        let (root, nodes) = single_entry(&key(7), &record());
+        assert_eq!(root.to_vec(), unhex("fbab42d1f0b444ca61e529e8e6627ad65c79bb9f6fe6b7f9b3a019ef9d4978c1"));
+        assert_eq!(read(&root, &nodes, &key(7)), Ok(Some(record())));
+        assert_eq!(read(&root, &nodes, &key(8)), Ok(None));
+    }
+}
@@ -0,0 +1,351 @@
+//! Storage for ciphertext chunks, addressed by BLAKE3 hash.
+//!
+//! Every backend re-hashes data on the way out, so a corrupted disk or a peer
+//! that served the wrong bytes is reported as [`StoreError::Corrupted`] rather
+//! than handed to the decryption layer.
+
+use crypto::ContentHash;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Reasons a store operation fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreError {
+    /// Stored bytes do not hash to their key.
+    Corrupted(ContentHash),
+    Io(String),
+    Backend(String),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Corrupted(hash) => write!(f, "Chunk {} failed its hash check", hash),
+            StoreError::Io(e) => write!(f, "I/O error: {}", e),
+            StoreError::Backend(e) => write!(f, "Storage backend error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        StoreError::Io(e.to_string())
+    }
+}
+
+/// A content-addressed chunk store.
+pub trait ChunkStore {
+    /// Store a chunk and return its hash. Storing an existing chunk is a no-op,
+    /// unless the stored copy is corrupted, in which case it is replaced.
+    fn put(&self, chunk: &[u8]) -> Result<ContentHash, StoreError>;
+
+    /// Read a whole chunk, verified against `hash`.
+    fn get(&self, hash: &ContentHash) -> Result<Option<Vec<u8>>, StoreError>;
+
+    fn has(&self, hash: &ContentHash) -> Result<bool, StoreError>;
+
+    /// Remove a chunk. Returns whether it was present.
+    fn delete(&self, hash: &ContentHash) -> Result<bool, StoreError>;
+
+    /// Stream a chunk. See [`VerifyingReader`] for when the data can be trusted.
+    fn reader(&self, hash: &ContentHash) -> Result<Option<VerifyingReader<Box<dyn Read + '_>>>, StoreError>;
+}
+
+/// Hashes data as it is read and fails at end of stream if the hash differs.
+///
+/// Bytes returned before the final `read` are unverified: callers must not
+/// act on them until the reader has returned `Ok(0)`.
+pub struct VerifyingReader<R> {
+    inner: R,
+    expected: ContentHash,
+    hasher: blake3::Hasher,
+    done: bool,
+}
+
+impl<R: Read> VerifyingReader<R> {
+    pub fn new(inner: R, expected: ContentHash) -> Self {
+        Self { inner, expected, hasher: blake3::Hasher::new(), done: false }
+    }
+}
+
+impl<R: Read> Read for VerifyingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.done {
+            return Ok(0);
+        }
+        let n = self.inner.read(buf)?;
+        if n > 0 {
+            self.hasher.update(&buf[..n]);
+            return Ok(n);
+        }
+        if self.hasher.finalize().as_bytes() != self.expected.as_bytes() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, StoreError::Corrupted(self.expected)));
+        }
+        self.done = true;
+        Ok(0)
+    }
+}
+
+fn verified(hash: &ContentHash, bytes: Vec<u8>) -> Result<Vec<u8>, StoreError> {
+    if ContentHash::hash(&bytes) == *hash {
+        Ok(bytes)
+    } else {
+        Err(StoreError::Corrupted(*hash))
+    }
+}
+
+/// Chunks held in memory. For tests and short-lived caches.
+#[derive(Default)]
+pub struct MemoryStore {
+    chunks: RwLock<HashMap<ContentHash, Arc<[u8]>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ChunkStore for MemoryStore {
+    fn put(&self, chunk: &[u8]) -> Result<ContentHash, StoreError> {
+        let hash = ContentHash::hash(chunk);
+        self.chunks.write().expect("lock poisoned").entry(hash).or_insert_with(|| chunk.into());
+        Ok(hash)
+    }
+
+    fn get(&self, hash: &ContentHash) -> Result<Option<Vec<u8>>, StoreError> {
+        let chunk = self.chunks.read().expect("lock poisoned").get(hash).cloned();
+        chunk.map(|c| verified(hash, c.to_vec())).transpose()
+    }
+
+    fn has(&self, hash: &ContentHash) -> Result<bool, StoreError> {
+        Ok(self.chunks.read().expect("lock poisoned").contains_key(hash))
+    }
+
+    fn delete(&self, hash: &ContentHash) -> Result<bool, StoreError> {
+        Ok(self.chunks.write().expect("lock poisoned").remove(hash).is_some())
+    }
+
+    fn reader(&self, hash: &ContentHash) -> Result<Option<VerifyingReader<Box<dyn Read + '_>>>, StoreError> {
+        let chunk = self.chunks.read().expect("lock poisoned").get(hash).cloned();
+        Ok(chunk.map(|c| VerifyingReader::new(Box::new(Cursor::new(c)) as Box<dyn Read>, *hash)))
+    }
+}
+
+/// Chunks as files under `root/ab/cd/<hex hash>`.
+///
+/// Writes go to a temporary file in the target directory, are synced, then
+/// renamed into place, so a crash never leaves a partial chunk under its hash.
+/// The directory is synced after the rename so the new entry survives a crash.
+pub struct FsStore {
+    root: PathBuf,
+}
+
+/// Distinguishes temporary files from concurrent writers in one process.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl FsStore {
+    pub fn open(root: impl AsRef<Path>) -> Result<Self, StoreError> {
+        fs::create_dir_all(root.as_ref())?;
+        Ok(Self { root: root.as_ref().to_path_buf() })
+    }
+
+    fn path(&self, hash: &ContentHash) -> PathBuf {
+        let name = hash.to_string();
+        self.root.join(&name[0..2]).join(&name[2..4]).join(name)
+    }
+}
+
+impl ChunkStore for FsStore {
+    fn put(&self, chunk: &[u8]) -> Result<ContentHash, StoreError> {
+        let hash = ContentHash::hash(chunk);
+        let path = self.path(&hash);
+        match fs::read(&path) {
+            Ok(stored) if ContentHash::hash(&stored) == hash => return Ok(hash),
+            // Corrupted: overwrite it below
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let dir = path.parent().expect("chunk paths have a parent");
+        fs::create_dir_all(dir)?;
+        let tmp = dir.join(format!(
+            ".{}.{}.{}.tmp",
+            hash,
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let result = (|| {
+            let mut file = File::create(&tmp)?;
+            file.write_all(chunk)?;
+            file.sync_all()?;
+            fs::rename(&tmp, &path)
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        result?;
+        sync_dir(dir)?;
+        Ok(hash)
+    }
+
+    fn get(&self, hash: &ContentHash) -> Result<Option<Vec<u8>>, StoreError> {
+        match fs::read(self.path(hash)) {
+            Ok(bytes) => verified(hash, bytes).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn has(&self, hash: &ContentHash) -> Result<bool, StoreError> {
+        Ok(self.path(hash).try_exists()?)
+    }
+
+    fn delete(&self, hash: &ContentHash) -> Result<bool, StoreError> {
+        match fs::remove_file(self.path(hash)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn reader(&self, hash: &ContentHash) -> Result<Option<VerifyingReader<Box<dyn Read + '_>>>, StoreError> {
+        match File::open(self.path(hash)) {
+            Ok(file) => Ok(Some(VerifyingReader::new(Box::new(BufReader::new(file)) as Box<dyn Read>, *hash))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Persist directory entries (creations, renames) in `dir`.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be opened for syncing here; renames are durable once
+/// the file system commits its metadata.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Chunks in a sled tree, keyed by raw hash bytes.
+#[cfg(feature = "sled")]
+pub struct SledStore {
+    tree: sled::Tree,
+}
+
+#[cfg(feature = "sled")]
+impl SledStore {
+    pub fn new(tree: sled::Tree) -> Self {
+        Self { tree }
+    }
+}
+
+#[cfg(feature = "sled")]
+impl ChunkStore for SledStore {
+    fn put(&self, chunk: &[u8]) -> Result<ContentHash, StoreError> {
+        let hash = ContentHash::hash(chunk);
+        let stored = self.tree.get(hash.as_bytes()).map_err(|e| StoreError::Backend(e.to_string()))?;
+        if stored.is_some_and(|v| ContentHash::hash(&v) == hash) {
+            return Ok(hash);
+        }
+        // Missing or corrupted
+        self.tree.insert(hash.as_bytes(), chunk).map_err(|e| StoreError::Backend(e.to_string()))?;
+        Ok(hash)
+    }
+
+    fn get(&self, hash: &ContentHash) -> Result<Option<Vec<u8>>, StoreError> {
+        let value = self.tree.get(hash.as_bytes()).map_err(|e| StoreError::Backend(e.to_string()))?;
+        value.map(|v| verified(hash, v.to_vec())).transpose()
+    }
+
+    fn has(&self, hash: &ContentHash) -> Result<bool, StoreError> {
+        self.tree.contains_key(hash.as_bytes()).map_err(|e| StoreError::Backend(e.to_string()))
+    }
+
+    fn delete(&self, hash: &ContentHash) -> Result<bool, StoreError> {
+        let old = self.tree.remove(hash.as_bytes()).map_err(|e| StoreError::Backend(e.to_string()))?;
+        Ok(old.is_some())
+    }
+
+    fn reader(&self, hash: &ContentHash) -> Result<Option<VerifyingReader<Box<dyn Read + '_>>>, StoreError> {
+        let value = self.tree.get(hash.as_bytes()).map_err(|e| StoreError::Backend(e.to_string()))?;
+        Ok(value.map(|v| VerifyingReader::new(Box::new(Cursor::new(v)) as Box<dyn Read>, *hash)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exercise(store: &dyn ChunkStore) {
+        let hash = store.put(b"chunk of ciphertext").unwrap();
+        assert_eq!(store.put(b"chunk of ciphertext").unwrap(), hash);
+        assert!(store.has(&hash).unwrap());
+        assert_eq!(store.get(&hash).unwrap().unwrap(), b"chunk of ciphertext");
+
+        let mut streamed = Vec::new();
+        store.reader(&hash).unwrap().unwrap().read_to_end(&mut streamed).unwrap();
+        assert_eq!(streamed, b"chunk of ciphertext");
+
+        assert!(store.delete(&hash).unwrap());
+        assert!(!store.has(&hash).unwrap());
+        assert_eq!(store.get(&hash).unwrap(), None);
+    }
+
+    #[test]
+    fn test_backends_roundtrip() {
+        exercise(&MemoryStore::new());
+
+        let dir = tempfile::tempdir().unwrap();
+        exercise(&FsStore::open(dir.path()).unwrap());
+
+        #[cfg(feature = "sled")]
+        {
+            let db = sled::Config::new().temporary(true).open().unwrap();
+            exercise(&SledStore::new(db.open_tree("chunks").unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_corruption_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsStore::open(dir.path()).unwrap();
+        let hash = store.put(b"original bytes").unwrap();
+        fs::write(store.path(&hash), b"tampered bytes").unwrap();
+
+        assert_eq!(store.get(&hash), Err(StoreError::Corrupted(hash)));
+
+        let mut reader = store.reader(&hash).unwrap().unwrap();
+        let err = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        assert_eq!(store.put(b"original bytes").unwrap(), hash);
+        assert_eq!(store.get(&hash).unwrap().unwrap(), b"original bytes");
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_sled_repairs_corrupted_chunk() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStore::new(db.open_tree("chunks").unwrap());
+        let hash = store.put(b"original bytes").unwrap();
+        store.tree.insert(hash.as_bytes(), &b"tampered bytes"[..]).unwrap();
+        assert_eq!(store.get(&hash), Err(StoreError::Corrupted(hash)));
+
+        assert_eq!(store.put(b"original bytes").unwrap(), hash);
+        assert_eq!(store.get(&hash).unwrap().unwrap(), b"original bytes");
+    }
+}
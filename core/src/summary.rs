@@ -0,0 +1,287 @@
+//! Signed post summaries (TECHNICAL_SPEC.md, PAYLOAD_SPECIFICATION.md).
+//!
+//! A summary is the small public header of a post: enough to show it in a feed
+//! without fetching the payload. Summaries are separate from manifests so they
+//! stay visible after the full content is revoked, and are capped so a header
+//! sync costs a bounded amount of bandwidth per post.
+
+use crate::content_type::ContentType;
+use crate::limits::SizeClass;
+use crypto::signatures::UserSecret;
+use crypto::wire::{tag, WireCodec, WireReader, WireWriter};
+use crypto::{ContentHash, CryptoError, IdentityKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const MAX_TITLE_BYTES: usize = 120;
+pub const MAX_DIGEST_BYTES: usize = 280;
+/// Long enough for any well-formed BCP 47 tag in practice.
+pub const MAX_LANGUAGE_BYTES: usize = 35;
+/// Upper bound on an encoded summary; larger input is rejected unparsed.
+pub const MAX_SUMMARY_BYTES: usize = 1024;
+
+/// Domain separator for summary signatures.
+const SIGNING_CONTEXT: &[u8] = b"share-summary-v1";
+
+/// A preview image stored as its own content-addressed blob.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thumbnail {
+    pub hash: ContentHash,
+    /// Must be an image type.
+    pub codec: ContentType,
+}
+
+/// An author-signed, revocation-independent post header.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub title: String,
+    /// Short plain-text digest of the post.
+    pub digest: String,
+    pub thumbnail: Option<Thumbnail>,
+    /// BCP 47 language tag, e.g. `en` or `pt-BR`.
+    pub language: String,
+    pub size_class: SizeClass,
+    pub author: IdentityKey,
+    /// Hash of the full manifest this summary describes.
+    pub manifest_hash: ContentHash,
+    /// Author's Ed25519 signature over all other fields.
+    #[serde(with = "serde_bytes")]
+    pub sig: [u8; 64],
+}
+
+/// Reasons a summary is refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SummaryError {
+    FieldTooLong { field: &'static str, len: usize, max: usize },
+    /// Title contains control characters.
+    InvalidTitle,
+    InvalidLanguage,
+    ThumbnailNotImage(ContentType),
+    /// Encoded summary exceeds `MAX_SUMMARY_BYTES`.
+    TooLarge(usize),
+    InvalidSignature,
+    Malformed(String),
+}
+
+impl fmt::Display for SummaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SummaryError::FieldTooLong { field, len, max } => {
+                write!(f, "Summary {} is {} bytes, limit is {}", field, len, max)
+            }
+            SummaryError::InvalidTitle => write!(f, "Summary title contains control characters"),
+            SummaryError::InvalidLanguage => write!(f, "Summary language is not a BCP 47 tag"),
+            SummaryError::ThumbnailNotImage(t) => write!(f, "Thumbnail codec {:?} is not an image type", t),
+            SummaryError::TooLarge(len) => {
+                write!(f, "Encoded summary is {} bytes, limit is {}", len, MAX_SUMMARY_BYTES)
+            }
+            SummaryError::InvalidSignature => write!(f, "Summary signature is invalid"),
+            SummaryError::Malformed(e) => write!(f, "Malformed summary: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SummaryError {}
+
+impl From<CryptoError> for SummaryError {
+    fn from(e: CryptoError) -> Self {
+        SummaryError::Malformed(e.to_string())
+    }
+}
+
+impl Summary {
+    /// Build and sign a summary as the holder of `secret`.
+    pub fn sign(
+        title: String,
+        digest: String,
+        thumbnail: Option<Thumbnail>,
+        language: String,
+        size_class: SizeClass,
+        manifest_hash: ContentHash,
+        secret: &UserSecret,
+    ) -> Result<Self, SummaryError> {
+        let mut summary = Summary {
+            title,
+            digest,
+            thumbnail,
+            language,
+            size_class,
+            author: secret.public_key(),
+            manifest_hash,
+            sig: [0u8; 64],
+        };
+        summary.validate()?;
+        summary.sig = secret.sign(&summary.signing_bytes()?);
+        Ok(summary)
+    }
+
+    /// Check field caps and formats.
+    pub fn validate(&self) -> Result<(), SummaryError> {
+        check_len("title", &self.title, MAX_TITLE_BYTES)?;
+        check_len("digest", &self.digest, MAX_DIGEST_BYTES)?;
+        check_len("language", &self.language, MAX_LANGUAGE_BYTES)?;
+
+        if self.title.chars().any(char::is_control) {
+            return Err(SummaryError::InvalidTitle);
+        }
+        if !is_language_tag(&self.language) {
+            return Err(SummaryError::InvalidLanguage);
+        }
+        if let Some(thumbnail) = &self.thumbnail {
+            if !matches!(thumbnail.codec, ContentType::WebP | ContentType::Avif) {
+                return Err(SummaryError::ThumbnailNotImage(thumbnail.codec));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check caps, then the author's signature.
+    pub fn verify(&self) -> Result<(), SummaryError> {
+        self.validate()?;
+        self.author
+            .verify(&self.signing_bytes()?, &self.sig)
+            .map_err(|_| SummaryError::InvalidSignature)
+    }
+
+    /// Decode a summary received from the network and verify it.
+    pub fn decode(bytes: &[u8]) -> Result<Self, SummaryError> {
+        if bytes.len() > MAX_SUMMARY_BYTES {
+            return Err(SummaryError::TooLarge(bytes.len()));
+        }
+        let summary = Summary::from_wire(bytes)?;
+        summary.verify()?;
+        Ok(summary)
+    }
+
+    /// The message the author signs: a context label, then every field but the signature.
+    fn signing_bytes(&self) -> Result<Vec<u8>, SummaryError> {
+        let mut writer = WireWriter::default();
+        writer.raw(SIGNING_CONTEXT);
+        self.encode_unsigned(&mut writer)?;
+        Ok(writer.into_bytes())
+    }
+
+    fn encode_unsigned(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.bytes(self.title.as_bytes())?;
+        writer.bytes(self.digest.as_bytes())?;
+        match &self.thumbnail {
+            None => writer.u8(0),
+            Some(thumbnail) => {
+                writer.u8(1);
+                writer.raw(thumbnail.hash.as_bytes());
+                writer.u8(thumbnail.codec as u8);
+            }
+        }
+        writer.bytes(self.language.as_bytes())?;
+        writer.u8(self.size_class as u8);
+        writer.raw(&self.author.as_bytes());
+        writer.raw(self.manifest_hash.as_bytes());
+        Ok(())
+    }
+}
+
+impl WireCodec for Summary {
+    const TAG: u8 = tag::SUMMARY;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        self.encode_unsigned(writer)?;
+        writer.raw(&self.sig);
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        let title = read_string(reader)?;
+        let digest = read_string(reader)?;
+        let thumbnail = match reader.u8()? {
+            0 => None,
+            1 => Some(Thumbnail {
+                hash: ContentHash::from_bytes(reader.array()?),
+                codec: ContentType::from_id(reader.u8()?)
+                    .ok_or_else(|| CryptoError::DecodingError("Unknown content type".into()))?,
+            }),
+            flag => return Err(CryptoError::DecodingError(format!("Invalid option flag {}", flag))),
+        };
+        Ok(Summary {
+            title,
+            digest,
+            thumbnail,
+            language: read_string(reader)?,
+            size_class: SizeClass::from_id(reader.u8()?)
+                .ok_or_else(|| CryptoError::DecodingError("Unknown size class".into()))?,
+            author: IdentityKey::from_bytes(&reader.array()?)?,
+            manifest_hash: ContentHash::from_bytes(reader.array()?),
+            sig: reader.array()?,
+        })
+    }
+}
+
+fn check_len(field: &'static str, value: &str, max: usize) -> Result<(), SummaryError> {
+    if value.len() > max {
+        return Err(SummaryError::FieldTooLong { field, len: value.len(), max });
+    }
+    Ok(())
+}
+
+/// ASCII alphanumeric subtags of 1-8 characters separated by `-`.
+fn is_language_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag
+            .split('-')
+            .all(|sub| (1..=8).contains(&sub.len()) && sub.bytes().all(|b| b.is_ascii_alphanumeric()))
+}
+
+fn read_string(reader: &mut WireReader<'_>) -> Result<String, CryptoError> {
+    String::from_utf8(reader.bytes()?).map_err(|_| CryptoError::DecodingError("Invalid UTF-8".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(secret: &UserSecret) -> Summary {
+        Summary::sign(
+            "Field notes".into(),
+            "Three days on the ridge, mostly fog.".into(),
+            Some(Thumbnail { hash: ContentHash::hash(b"thumb"), codec: ContentType::WebP }),
+            "en".into(),
+            SizeClass::Image,
+            ContentHash::hash(b"manifest"),
+            secret,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_summary_roundtrip_and_tamper() {
+        let secret = UserSecret::generate();
+        let summary = sample(&secret);
+
+        let bytes = summary.to_wire().unwrap();
+        assert!(bytes.len() <= MAX_SUMMARY_BYTES);
+        assert_eq!(Summary::decode(&bytes).unwrap(), summary);
+
+        let mut forged = summary.clone();
+        forged.title = "Something else".into();
+        assert_eq!(forged.verify(), Err(SummaryError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_summary_caps() {
+        let secret = UserSecret::generate();
+        let too_long = Summary::sign(
+            "t".repeat(MAX_TITLE_BYTES + 1),
+            String::new(),
+            None,
+            "en".into(),
+            SizeClass::Tiny,
+            ContentHash::hash(b"manifest"),
+            &secret,
+        );
+        assert!(matches!(too_long, Err(SummaryError::FieldTooLong { field: "title", .. })));
+
+        let mut summary = sample(&secret);
+        summary.thumbnail = Some(Thumbnail { hash: ContentHash::hash(b"x"), codec: ContentType::Av1 });
+        assert_eq!(summary.validate(), Err(SummaryError::ThumbnailNotImage(ContentType::Av1)));
+        assert_eq!(Summary::decode(&[0u8; MAX_SUMMARY_BYTES + 1]), Err(SummaryError::TooLarge(MAX_SUMMARY_BYTES + 1)));
+    }
+}
@@ -0,0 +1,289 @@
+//! Conversation threads built from reply links between manifests.
+//!
+//! A thread is every manifest whose `thread_root` names the same first post,
+//! arranged by `in_reply_to`. Posts that are missing, revoked or from
+//! blacklisted authors keep their place in the tree as stubs (with their
+//! summary, if one is known) so the replies beneath them stay readable.
+//!
+//! Trees are built and dropped without recursion, so a hostile reply chain
+//! of any depth cannot overflow the stack.
+
+use crate::manifest::Manifest;
+use crate::summary::Summary;
+use crypto::signatures::IdentityKey;
+use crypto::ContentHash;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Why a post is shown as a stub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StubReason {
+    /// The manifest is not available locally, or does not verify.
+    Missing,
+    Revoked,
+    /// The author is on a governance blacklist.
+    Blacklisted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeContent {
+    Post(Manifest),
+    Stub { reason: StubReason, summary: Option<Summary> },
+}
+
+/// One post in a conversation and the replies to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadNode {
+    /// Manifest hash of this post.
+    pub hash: ContentHash,
+    pub content: NodeContent,
+    /// Direct replies, ordered by manifest hash.
+    pub replies: Vec<ThreadNode>,
+}
+
+impl ThreadNode {
+    /// Number of nodes in this subtree, stubs included.
+    pub fn node_count(&self) -> usize {
+        let mut count = 0;
+        let mut pending = vec![self];
+        while let Some(node) = pending.pop() {
+            count += 1;
+            pending.extend(&node.replies);
+        }
+        count
+    }
+}
+
+impl Drop for ThreadNode {
+    fn drop(&mut self) {
+        let mut pending = std::mem::take(&mut self.replies);
+        while let Some(mut node) = pending.pop() {
+            pending.append(&mut node.replies);
+        }
+    }
+}
+
+/// Build the conversation tree rooted at `root`.
+///
+/// `hidden` tells whether a manifest must be shown as a stub (the caller checks
+/// its revocation lists and blacklists). Replies whose parent is not part of
+/// the thread hang under a stub for that parent, directly below the root;
+/// posts naming the thread but replying to nothing hang under the root.
+/// Reply links cannot form cycles: a manifest's hash covers its `in_reply_to`,
+/// so a post can only reply to posts that existed before it.
+///
+/// A summary is only shown if its author wrote the post: the manifest's
+/// author, or for posts not in `manifests` the author recorded in `authors`
+/// (e.g. from the post's on-chain registration). Of several such summaries,
+/// the one with the lowest signature bytes is shown, whatever the input order.
+pub fn assemble<F>(
+    root: &ContentHash,
+    manifests: &[Manifest],
+    summaries: &[Summary],
+    authors: &HashMap<ContentHash, IdentityKey>,
+    hidden: F,
+) -> ThreadNode
+where
+    F: Fn(&Manifest) -> Option<StubReason>,
+{
+    let by_hash: HashMap<ContentHash, &Manifest> = manifests
+        .iter()
+        .filter(|m| m.verify().is_ok())
+        .map(|m| (m.hash(), m))
+        .collect();
+
+    let mut summary_of: HashMap<ContentHash, &Summary> = HashMap::new();
+    for summary in summaries {
+        let author = match by_hash.get(&summary.manifest_hash) {
+            Some(manifest) => Some(&manifest.auth_id),
+            None => authors.get(&summary.manifest_hash),
+        };
+        if author != Some(&summary.author) || summary.verify().is_err() {
+            continue;
+        }
+        summary_of
+            .entry(summary.manifest_hash)
+            .and_modify(|kept| {
+                if summary.sig < kept.sig {
+                    *kept = summary;
+                }
+            })
+            .or_insert(summary);
+    }
+
+    // Replies by parent, keyed by hash bytes for a deterministic order
+    let mut children: HashMap<ContentHash, BTreeSet<[u8; 32]>> = HashMap::new();
+    for (hash, manifest) in &by_hash {
+        if manifest.thread_root == Some(*root) && hash != root {
+            let parent = manifest.in_reply_to.unwrap_or(*root);
+            children.entry(parent).or_default().insert(*hash.as_bytes());
+        }
+    }
+
+    let builder = Builder { root: *root, by_hash, summary_of, children, hidden };
+    let orphan_parents: BTreeMap<[u8; 32], ContentHash> = builder
+        .children
+        .keys()
+        .filter(|parent| !builder.in_thread(parent))
+        .map(|parent| (*parent.as_bytes(), *parent))
+        .collect();
+
+    let mut tree = builder.node(*root);
+    for parent in orphan_parents.into_values() {
+        tree.replies.push(builder.node(parent));
+    }
+    tree
+}
+
+struct Builder<'a, F> {
+    root: ContentHash,
+    by_hash: HashMap<ContentHash, &'a Manifest>,
+    summary_of: HashMap<ContentHash, &'a Summary>,
+    children: HashMap<ContentHash, BTreeSet<[u8; 32]>>,
+    hidden: F,
+}
+
+impl<F> Builder<'_, F>
+where
+    F: Fn(&Manifest) -> Option<StubReason>,
+{
+    fn in_thread(&self, hash: &ContentHash) -> bool {
+        *hash == self.root || self.by_hash.get(hash).is_some_and(|m| m.thread_root == Some(self.root))
+    }
+
+    /// Build the subtree below `hash` depth-first with an explicit stack.
+    fn node(&self, hash: ContentHash) -> ThreadNode {
+        let mut stack = vec![self.open(hash)];
+        loop {
+            let top = stack.last_mut().expect("stack holds at least the subtree root");
+            if let Some(child) = top.1.pop() {
+                stack.push(self.open(child));
+                continue;
+            }
+            let (done, _) = stack.pop().expect("stack holds at least the subtree root");
+            match stack.last_mut() {
+                Some((parent, _)) => parent.replies.push(done),
+                None => return done,
+            }
+        }
+    }
+
+    /// A node without its replies, and the hashes of those replies in
+    /// reverse order (so popping yields them in hash order).
+    fn open(&self, hash: ContentHash) -> (ThreadNode, Vec<ContentHash>) {
+        let reason = match self.by_hash.get(&hash) {
+            Some(manifest) if self.in_thread(&hash) => (self.hidden)(manifest),
+            _ => Some(StubReason::Missing),
+        };
+        let content = match reason {
+            None => NodeContent::Post(self.by_hash[&hash].clone()),
+            Some(reason) => NodeContent::Stub {
+                reason,
+                summary: self.summary_of.get(&hash).map(|s| (*s).clone()),
+            },
+        };
+
+        let pending = self
+            .children
+            .get(&hash)
+            .into_iter()
+            .flatten()
+            .rev()
+            .map(|child| ContentHash::from_bytes(*child))
+            .collect();
+
+        (ThreadNode { hash, content, replies: Vec::new() }, pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::SizeClass;
+    use crate::manifest::test_manifest;
+    use crypto::signatures::UserSecret;
+
+    fn post(secret: &UserSecret, body: &[u8], parent: Option<&Manifest>) -> Manifest {
+        test_manifest(secret, body, |m| {
+            m.in_reply_to = parent.map(Manifest::hash);
+            m.thread_root = parent.map(|p| p.thread_root.unwrap_or_else(|| p.hash()));
+        })
+    }
+
+    #[test]
+    fn test_assemble_keeps_replies_under_stubs() {
+        let alice = UserSecret::generate();
+        let bob = UserSecret::generate();
+        let root = post(&alice, b"root", None);
+        let lost = post(&bob, b"lost reply", Some(&root));
+        let orphan = post(&alice, b"reply to lost", Some(&lost));
+        let banned = post(&bob, b"banned reply", Some(&root));
+
+        let summary = |title: &str, secret: &UserSecret| {
+            Summary::sign(title.into(), String::new(), None, "en".into(), SizeClass::Tiny, lost.hash(), secret)
+                .unwrap()
+        };
+        let by_bob = summary("Lost", &bob);
+        let by_mallory = summary("Spam", &UserSecret::generate());
+        let authors = HashMap::from([(lost.hash(), bob.public_key())]);
+
+        // Names the thread without replying to anything
+        let stray = test_manifest(&alice, b"stray", |m| m.thread_root = Some(root.hash()));
+
+        let banned_hash = banned.hash();
+        let tree = assemble(
+            &root.hash(),
+            &[root.clone(), orphan.clone(), banned, stray.clone()],
+            &[by_mallory, by_bob.clone()],
+            &authors,
+            |m| (m.hash() == banned_hash).then_some(StubReason::Blacklisted),
+        );
+
+        assert_eq!(tree.content, NodeContent::Post(root));
+        assert_eq!(tree.node_count(), 5);
+        assert!(tree.replies.iter().any(|n| n.content == NodeContent::Post(stray.clone())));
+
+        let lost_node = tree.replies.iter().find(|n| n.hash == lost.hash()).unwrap();
+        assert_eq!(
+            lost_node.content,
+            NodeContent::Stub { reason: StubReason::Missing, summary: Some(by_bob) }
+        );
+        assert_eq!(lost_node.replies[0].content, NodeContent::Post(orphan));
+
+        let banned_node = tree.replies.iter().find(|n| n.hash == banned_hash).unwrap();
+        assert!(matches!(banned_node.content, NodeContent::Stub { reason: StubReason::Blacklisted, .. }));
+    }
+
+    #[test]
+    fn test_summary_of_unknown_author_is_dropped() {
+        let alice = UserSecret::generate();
+        let root = post(&alice, b"root", None);
+        let gone = ContentHash::hash(b"never seen");
+        let summary =
+            Summary::sign("Claim".into(), String::new(), None, "en".into(), SizeClass::Tiny, gone, &alice).unwrap();
+        let reply = test_manifest(&alice, b"reply", |m| {
+            m.in_reply_to = Some(gone);
+            m.thread_root = Some(root.hash());
+        });
+
+        let tree = assemble(&root.hash(), &[root.clone(), reply], &[summary], &HashMap::new(), |_| None);
+        assert_eq!(tree.replies[0].content, NodeContent::Stub { reason: StubReason::Missing, summary: None });
+    }
+
+    #[test]
+    fn test_deep_reply_chain_does_not_recurse() {
+        let alice = UserSecret::generate();
+        let root = post(&alice, b"root", None);
+        let mut chain = vec![root.clone()];
+        for i in 0..300u32 {
+            let reply = post(&alice, &i.to_le_bytes(), chain.last());
+            chain.push(reply);
+        }
+
+        // Far less stack than a recursive build of this depth needs
+        let handle = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || assemble(&root.hash(), &chain, &[], &HashMap::new(), |_| None).node_count())
+            .unwrap();
+        assert_eq!(handle.join().unwrap(), 301);
+    }
+}
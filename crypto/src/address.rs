@@ -0,0 +1,180 @@
+//! Human-friendly identity encodings.
+//!
+//! * Addresses: bech32m with a network prefix, e.g. `share1...`. The checksum
+//!   catches typos, and the prefix stops an identity from one network (or fork)
+//!   being used on another.
+//! * Fingerprints and safety numbers: short codes users compare out of band to
+//!   confirm they hold the same key.
+
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32m, Hrp};
+use crate::hashing::ContentHash;
+use crate::signatures::IdentityKey;
+use crate::CryptoError;
+
+/// BLAKE3 key-derivation context for fingerprints.
+const FINGERPRINT_CONTEXT: &str = "share identity fingerprint v1";
+
+/// Digits per key in a safety number (6 groups of 5).
+const SAFETY_DIGITS_PER_KEY: usize = 30;
+
+/// The network an address belongs to, expressed as its bech32 prefix.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NetworkId(Hrp);
+
+impl NetworkId {
+    /// The main network.
+    pub fn mainnet() -> Self {
+        NetworkId(Hrp::parse_unchecked("share"))
+    }
+
+    /// Public test networks.
+    pub fn testnet() -> Self {
+        NetworkId(Hrp::parse_unchecked("tshare"))
+    }
+
+    /// A ratified fork, identified by its genesis hash.
+    ///
+    /// The prefix is `share` followed by the first 4 bytes of the hash in hex,
+    /// so addresses from different forks are never interchangeable.
+    pub fn for_fork(genesis_hash: &ContentHash) -> Self {
+        let prefix = format!("share{}", hex::encode(&genesis_hash.as_bytes()[..4]));
+        NetworkId(Hrp::parse(&prefix).expect("ASCII hex prefix is a valid HRP"))
+    }
+
+    pub fn prefix(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl IdentityKey {
+    /// Encode as a bech32m address for the given network.
+    pub fn to_address(&self, network: &NetworkId) -> String {
+        bech32::encode::<Bech32m>(network.0, &self.as_bytes())
+            .expect("32-byte payload is within bech32 length limits")
+    }
+
+    /// Parse an identity from user input.
+    ///
+    /// Accepts a bech32m address for `network`, or the legacy 64-character hex
+    /// form (which carries no checksum or network and should be re-displayed as
+    /// an address once parsed).
+    pub fn parse(input: &str, network: &NetworkId) -> Result<Self, CryptoError> {
+        let input = input.trim();
+
+        if input.len() == 64 && input.bytes().all(|b| b.is_ascii_hexdigit()) {
+            let mut bytes = [0u8; 32];
+            hex::decode_to_slice(input, &mut bytes)
+                .map_err(|e| CryptoError::DecodingError(e.to_string()))?;
+            return IdentityKey::from_bytes(&bytes);
+        }
+
+        let checked = CheckedHrpstring::new::<Bech32m>(input)
+            .map_err(|e| CryptoError::DecodingError(format!("Invalid address: {}", e)))?;
+        if checked.hrp() != network.0 {
+            return Err(CryptoError::WrongNetwork {
+                expected: network.prefix().to_string(),
+                found: checked.hrp().to_lowercase(),
+            });
+        }
+
+        let data: Vec<u8> = checked.byte_iter().collect();
+        let bytes: [u8; 32] = data
+            .try_into()
+            .map_err(|_| CryptoError::DecodingError("Address payload is not 32 bytes".into()))?;
+        IdentityKey::from_bytes(&bytes)
+    }
+
+    /// A short fingerprint for display next to a name, e.g. `3F2A-91C0-7B44-E615`.
+    ///
+    /// 64 bits: enough to spot a substituted key at a glance, not a replacement
+    /// for comparing the full safety number.
+    pub fn fingerprint(&self) -> String {
+        let digest = blake3::derive_key(FINGERPRINT_CONTEXT, &self.as_bytes());
+        digest[..8]
+            .chunks(2)
+            .map(hex::encode_upper)
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// A 60-digit safety number two users compare to verify each other's keys.
+    ///
+    /// Each key contributes 30 digits; the halves are ordered so both users see
+    /// the same number regardless of who computes it.
+    pub fn safety_number(&self, other: &IdentityKey) -> String {
+        let mut halves = [safety_digits(self), safety_digits(other)];
+        halves.sort();
+        let digits = halves.concat();
+
+        digits
+            .as_bytes()
+            .chunks(5)
+            .map(|group| std::str::from_utf8(group).expect("ASCII digits"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// 30 decimal digits derived from a key: six 5-byte chunks, each reduced mod 100000.
+fn safety_digits(key: &IdentityKey) -> String {
+    let digest = blake3::derive_key(FINGERPRINT_CONTEXT, &key.as_bytes());
+    let digits: String = digest[..30]
+        .chunks(5)
+        .map(|chunk| {
+            let value = chunk.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+            format!("{:05}", value % 100_000)
+        })
+        .collect();
+    debug_assert_eq!(digits.len(), SAFETY_DIGITS_PER_KEY);
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signatures::UserSecret;
+
+    #[test]
+    fn test_address_roundtrip_and_legacy_hex() {
+        let key = UserSecret::generate().public_key();
+        let network = NetworkId::mainnet();
+
+        let address = key.to_address(&network);
+        assert!(address.starts_with("share1"));
+        assert_eq!(IdentityKey::parse(&address, &network).unwrap(), key);
+
+        let legacy = hex::encode(key.as_bytes());
+        assert_eq!(IdentityKey::parse(&legacy, &network).unwrap(), key);
+    }
+
+    #[test]
+    fn test_address_rejects_typo_and_wrong_network() {
+        let key = UserSecret::generate().public_key();
+        let address = key.to_address(&NetworkId::mainnet());
+
+        // Swap one data character for another valid bech32 character
+        let mut typo: Vec<char> = address.chars().collect();
+        let i = typo.len() - 10;
+        typo[i] = if typo[i] == 'q' { 'p' } else { 'q' };
+        let typo: String = typo.into_iter().collect();
+        assert!(IdentityKey::parse(&typo, &NetworkId::mainnet()).is_err());
+
+        let fork = NetworkId::for_fork(&ContentHash::hash(b"fork genesis"));
+        assert!(matches!(
+            IdentityKey::parse(&address, &fork),
+            Err(CryptoError::WrongNetwork { .. })
+        ));
+    }
+
+    #[test]
+    fn test_safety_number_is_symmetric() {
+        let alice = UserSecret::generate().public_key();
+        let bob = UserSecret::generate().public_key();
+
+        let number = alice.safety_number(&bob);
+        assert_eq!(number, bob.safety_number(&alice));
+        assert_eq!(number.split(' ').count(), 12);
+        assert_eq!(alice.fingerprint().len(), 19);
+    }
+}
@@ -0,0 +1,142 @@
+use ed25519_dalek::{Signature, VerifyingKey};
+use crate::signatures::{CanonicalSignature, IdentityKey};
+use crate::CryptoError;
+
+/// A single (key, message, signature) triple queued for batch verification.
+#[derive(Clone, Copy, Debug)]
+pub struct BatchItem<'a> {
+    /// The identity that allegedly signed the message.
+    pub key: &'a IdentityKey,
+    /// The signed bytes (e.g., a canonical manifest or a ballot).
+    pub message: &'a [u8],
+    /// The 64-byte Ed25519 signature.
+    pub signature: &'a [u8; 64],
+}
+
+/// Verify many Ed25519 signatures at once.
+///
+/// Every item is first screened with the same encoding rules as
+/// [`IdentityKey::verify`] (see [`CanonicalSignature`]), so the batch equation
+/// can never accept a signature that a single strict check would reject. Keys
+/// need no screening because `IdentityKey` already refuses weak keys. If the
+/// batch equation fails, the batch is bisected until the offending items are
+/// isolated.
+///
+/// # Returns
+/// `Ok(())` if every signature is valid, otherwise
+/// `CryptoError::BatchVerificationError` carrying the indices (into `items`) of
+/// every invalid signature, in ascending order.
+pub fn verify_batch(items: &[BatchItem<'_>]) -> Result<(), CryptoError> {
+    let mut failed = Vec::new();
+    let mut candidates = Vec::with_capacity(items.len());
+
+    // 1. Screen out malleable or malformed items before they reach the batch equation
+    for (index, item) in items.iter().enumerate() {
+        if CanonicalSignature::from_bytes(item.signature).is_ok() {
+            candidates.push(index);
+        } else {
+            failed.push(index);
+        }
+    }
+
+    // 2. Verify the remainder in one multiscalar multiplication, bisecting on failure
+    if !candidates.is_empty() && batch_equation_holds(items, &candidates).is_err() {
+        bisect(items, &candidates, &mut failed);
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        failed.sort_unstable();
+        Err(CryptoError::BatchVerificationError(failed))
+    }
+}
+
+/// Recursively split a failing batch until each invalid item is found.
+fn bisect(items: &[BatchItem<'_>], indices: &[usize], failed: &mut Vec<usize>) {
+    if let [index] = indices {
+        // A batch of one is authoritative only under single strict verification
+        if verify_single_strict(&items[*index]).is_err() {
+            failed.push(*index);
+        }
+        return;
+    }
+
+    let (left, right) = indices.split_at(indices.len() / 2);
+    for half in [left, right] {
+        if batch_equation_holds(items, half).is_err() {
+            bisect(items, half, failed);
+        }
+    }
+}
+
+/// Run ed25519-dalek's randomized batch equation over a subset of items.
+fn batch_equation_holds(items: &[BatchItem<'_>], indices: &[usize]) -> Result<(), CryptoError> {
+    let messages: Vec<&[u8]> = indices.iter().map(|&i| items[i].message).collect();
+    let signatures: Vec<Signature> = indices
+        .iter()
+        .map(|&i| Signature::from_bytes(items[i].signature))
+        .collect();
+    let keys: Vec<VerifyingKey> = indices
+        .iter()
+        .map(|&i| verifying_key(items[i].key))
+        .collect::<Result<_, _>>()?;
+
+    ed25519_dalek::verify_batch(&messages, &signatures, &keys)
+        .map_err(|_| CryptoError::InvalidSignature)
+}
+
+/// Strict single-signature verification used at the leaves of the bisection.
+fn verify_single_strict(item: &BatchItem<'_>) -> Result<(), CryptoError> {
+    item.key.verify(item.message, item.signature)
+}
+
+fn verifying_key(key: &IdentityKey) -> Result<VerifyingKey, CryptoError> {
+    VerifyingKey::from_bytes(&key.as_bytes()).map_err(|_| CryptoError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signatures::UserSecret;
+
+    #[test]
+    fn test_batch_accepts_valid_signatures() {
+        let secrets: Vec<UserSecret> = (0..16).map(|_| UserSecret::generate()).collect();
+        let keys: Vec<IdentityKey> = secrets.iter().map(|s| s.public_key()).collect();
+        let messages: Vec<Vec<u8>> = (0..16u8).map(|i| vec![i; 8]).collect();
+        let sigs: Vec<[u8; 64]> = secrets
+            .iter()
+            .zip(&messages)
+            .map(|(s, m)| s.sign(m))
+            .collect();
+
+        let items: Vec<BatchItem> = (0..16)
+            .map(|i| BatchItem { key: &keys[i], message: &messages[i], signature: &sigs[i] })
+            .collect();
+
+        assert!(verify_batch(&items).is_ok());
+        assert!(verify_batch(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_batch_reports_failed_indices() {
+        let secrets: Vec<UserSecret> = (0..10).map(|_| UserSecret::generate()).collect();
+        let keys: Vec<IdentityKey> = secrets.iter().map(|s| s.public_key()).collect();
+        let message = b"Ballot: proposal #42, in favor";
+        let mut sigs: Vec<[u8; 64]> = secrets.iter().map(|s| s.sign(message)).collect();
+
+        // Corrupt two signatures: one flipped S bit, one signed by the wrong key
+        sigs[3][40] ^= 0x01;
+        sigs[7] = secrets[0].sign(message);
+
+        let items: Vec<BatchItem> = (0..10)
+            .map(|i| BatchItem { key: &keys[i], message, signature: &sigs[i] })
+            .collect();
+
+        match verify_batch(&items) {
+            Err(CryptoError::BatchVerificationError(failed)) => assert_eq!(failed, vec![3, 7]),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
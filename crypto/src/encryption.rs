@@ -4,6 +4,7 @@ use chacha20poly1305::{
 };
 use rand::{rngs::OsRng, RngCore};
 use serde::{Deserialize, Serialize};
+use crate::suite::{AeadAlgorithm, CipherSuite};
 use crate::CryptoError;
 
 /// The size of the symmetric key in bytes (32 bytes = 256 bits).
@@ -14,6 +15,10 @@ pub const NONCE_SIZE: usize = 24;
 /// Represents an encrypted payload, including the unique nonce used.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Ciphertext {
+    /// The cipher suite this payload was encrypted under.
+    /// Ciphertexts written before suite IDs existed default to the legacy suite.
+    #[serde(default = "CipherSuite::legacy")]
+    pub suite: CipherSuite,
     /// The random 24-byte nonce used for this specific encryption.
     pub nonce: Vec<u8>,
     /// The actual encrypted data (including the Poly1305 tag).
@@ -43,7 +48,7 @@ impl SymmetricKey {
     }
 }
 
-/// Encrypts data using XChaCha20-Poly1305.
+/// Encrypts data under the current cipher suite.
 ///
 /// # Arguments
 /// * `plaintext` - The data to encrypt.
@@ -54,6 +59,49 @@ pub fn encrypt(
     key: &SymmetricKey,
     aad: &[u8],
 ) -> Result<Ciphertext, CryptoError> {
+    encrypt_with_suite(CipherSuite::CURRENT, plaintext, key, aad)
+}
+
+/// Encrypts data under an explicit cipher suite.
+///
+/// Deprecated suites are refused: they remain readable but must not be used for new content.
+pub fn encrypt_with_suite(
+    suite: CipherSuite,
+    plaintext: &[u8],
+    key: &SymmetricKey,
+    aad: &[u8],
+) -> Result<Ciphertext, CryptoError> {
+    suite.ensure_active()?;
+
+    let (nonce, data) = match suite.aead() {
+        AeadAlgorithm::XChaCha20Poly1305 => xchacha_encrypt(plaintext, key, aad)?,
+    };
+
+    Ok(Ciphertext { suite, nonce, data })
+}
+
+/// Decrypts data, dispatching on the suite recorded in the ciphertext.
+///
+/// # Arguments
+/// * `ciphertext` - The encrypted struct containing data and nonce.
+/// * `key` - The symmetric key (DEK).
+/// * `aad` - The EXACT same AAD used during encryption. Mismatches will cause failure.
+pub fn decrypt(
+    ciphertext: &Ciphertext,
+    key: &SymmetricKey,
+    aad: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    match ciphertext.suite.aead() {
+        AeadAlgorithm::XChaCha20Poly1305 => xchacha_decrypt(ciphertext, key, aad),
+    }
+}
+
+/// XChaCha20-Poly1305 encryption. Returns `(nonce, ciphertext || tag)`.
+fn xchacha_encrypt(
+    plaintext: &[u8],
+    key: &SymmetricKey,
+    aad: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
     // 1. Initialize the cipher with the key
     let cipher = XChaCha20Poly1305::new(key.as_bytes().into());
 
@@ -73,19 +121,11 @@ pub fn encrypt(
         .encrypt(nonce, payload)
         .map_err(|_| CryptoError::EncryptionError("AEAD encryption failure".into()))?;
 
-    Ok(Ciphertext {
-        nonce: nonce_bytes.to_vec(),
-        data: encrypted_data,
-    })
+    Ok((nonce_bytes.to_vec(), encrypted_data))
 }
 
-/// Decrypts data using XChaCha20-Poly1305.
-///
-/// # Arguments
-/// * `ciphertext` - The encrypted struct containing data and nonce.
-/// * `key` - The symmetric key (DEK).
-/// * `aad` - The EXACT same AAD used during encryption. Mismatches will cause failure.
-pub fn decrypt(
+/// XChaCha20-Poly1305 decryption and tag verification.
+fn xchacha_decrypt(
     ciphertext: &Ciphertext,
     key: &SymmetricKey,
     aad: &[u8],
@@ -145,4 +185,19 @@ mod tests {
         let result = decrypt(&ciphertext, &key, aad_wrong);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_legacy_ciphertext_without_suite() {
+        let key = SymmetricKey::generate();
+        let ciphertext = encrypt(b"Old post", &key, b"").unwrap();
+        assert_eq!(ciphertext.suite, CipherSuite::CURRENT);
+
+        // Ciphertexts serialized before suite IDs existed carry no `suite` field
+        let mut json: serde_json::Value = serde_json::to_value(&ciphertext).unwrap();
+        json.as_object_mut().unwrap().remove("suite");
+        let legacy: Ciphertext = serde_json::from_value(json).unwrap();
+
+        assert_eq!(legacy.suite, CipherSuite::legacy());
+        assert_eq!(decrypt(&legacy, &key, b"").unwrap(), b"Old post");
+    }
 }
\ No newline at end of file
@@ -1,4 +1,7 @@
 use crate::encryption::{SymmetricKey, KEY_SIZE};
+#[cfg(feature = "pq-hybrid")]
+use crate::kdf::derive_hybrid_kek;
+use crate::suite::{CipherSuite, KemAlgorithm};
 use crate::CryptoError;
 use aes_kw::KekAes256;
 use rand::rngs::OsRng;
@@ -9,6 +12,11 @@ use x25519_dalek::{PublicKey, StaticSecret};
 /// Represents a secure transmission of a symmetric key to a recipient.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Envelope {
+    /// The cipher suite whose KEM sealed this envelope.
+    /// Envelopes written before suite IDs existed default to the legacy suite.
+    #[serde(default = "CipherSuite::legacy")]
+    pub suite: CipherSuite,
+
     /// The ephemeral public key of the sender (for this specific envelope).
     /// Allows the recipient to perform ECDH to recover the KEK.
     pub ephemeral_pub: [u8; 32],
@@ -22,6 +30,10 @@ pub struct Envelope {
     /// The wrapped Data Encryption Key (DEK).
     /// Encrypted using AES-KW with the derived KEK.
     pub wrapped_key: Vec<u8>,
+
+    /// The ML-KEM-768 ciphertext for hybrid suites; absent for classic envelopes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pq_ciphertext: Option<Vec<u8>>,
 }
 
 impl Envelope {
@@ -35,6 +47,125 @@ impl Envelope {
         dek: &SymmetricKey,
         recipient_pub_bytes: [u8; 32],
         expiry: u64,
+    ) -> Result<Self, CryptoError> {
+        let suite = CipherSuite::CURRENT;
+        suite.ensure_active()?;
+
+        match suite.kem() {
+            KemAlgorithm::X25519Sha256AesKw => Self::seal_x25519(suite, dek, recipient_pub_bytes, expiry),
+            KemAlgorithm::X25519MlKem768 => Err(CryptoError::EncryptionError(
+                "Hybrid envelopes are sealed with seal_hybrid".into(),
+            )),
+        }
+    }
+
+    /// Open an envelope to recover the SymmetricKey, dispatching on its suite.
+    ///
+    /// Hybrid envelopes also need the recipient's ML-KEM key; use `open_hybrid`.
+    ///
+    /// # Arguments
+    /// * `recipient_secret_bytes` - The recipient's X25519 private key.
+    pub fn open(
+        &self,
+        recipient_secret_bytes: [u8; 32],
+    ) -> Result<SymmetricKey, CryptoError> {
+        match self.suite.kem() {
+            KemAlgorithm::X25519Sha256AesKw => self.open_x25519(recipient_secret_bytes),
+            KemAlgorithm::X25519MlKem768 => Err(CryptoError::DecryptionError(
+                "Hybrid envelope requires the recipient's ML-KEM key".into(),
+            )),
+        }
+    }
+
+    /// Create a hybrid (X25519 + ML-KEM-768) envelope.
+    ///
+    /// The KEK is derived from both shared secrets (see `kdf::derive_hybrid_kek`),
+    /// so the DEK stays protected unless both X25519 and ML-KEM are broken.
+    ///
+    /// # Arguments
+    /// * `dek` - The Data Encryption Key to protect.
+    /// * `recipient_pub_bytes` - The recipient's X25519 public key bytes.
+    /// * `recipient_pq_pub` - The recipient's ML-KEM-768 encapsulation key.
+    /// * `expiry` - Expiration timestamp.
+    #[cfg(feature = "pq-hybrid")]
+    pub fn seal_hybrid(
+        dek: &SymmetricKey,
+        recipient_pub_bytes: [u8; 32],
+        recipient_pq_pub: &[u8],
+        expiry: u64,
+    ) -> Result<Self, CryptoError> {
+        let suite = CipherSuite::HybridPq;
+        suite.ensure_active()?;
+
+        // 1. Classic half: ephemeral X25519 ECDH
+        let ephemeral_secret = StaticSecret::random_from_rng(OsRng);
+        let ephemeral_pub = PublicKey::from(&ephemeral_secret);
+        let x25519_secret = ephemeral_secret.diffie_hellman(&PublicKey::from(recipient_pub_bytes));
+
+        // 2. Post-quantum half: ML-KEM-768 encapsulation
+        let (pq_ciphertext, pq_secret) = pq::encapsulate(recipient_pq_pub)?;
+
+        // 3. Combine both shared secrets into the KEK
+        let kek_bytes = derive_hybrid_kek(
+            x25519_secret.as_bytes(),
+            &pq_secret,
+            ephemeral_pub.as_bytes(),
+            &recipient_pub_bytes,
+            &pq_ciphertext,
+        );
+
+        Ok(Envelope {
+            suite,
+            ephemeral_pub: *ephemeral_pub.as_bytes(),
+            recipient_id: recipient_pub_bytes,
+            expiry,
+            wrapped_key: wrap_dek(kek_bytes, dek)?,
+            pq_ciphertext: Some(pq_ciphertext),
+        })
+    }
+
+    /// Open any envelope given both of the recipient's secret keys.
+    ///
+    /// Classic envelopes ignore `recipient_pq_secret`, so clients holding both keys
+    /// can use this for every envelope they receive.
+    ///
+    /// # Arguments
+    /// * `recipient_secret_bytes` - The recipient's X25519 private key.
+    /// * `recipient_pq_secret` - The recipient's ML-KEM-768 decapsulation key.
+    #[cfg(feature = "pq-hybrid")]
+    pub fn open_hybrid(
+        &self,
+        recipient_secret_bytes: [u8; 32],
+        recipient_pq_secret: &[u8],
+    ) -> Result<SymmetricKey, CryptoError> {
+        match self.suite.kem() {
+            KemAlgorithm::X25519Sha256AesKw => self.open_x25519(recipient_secret_bytes),
+            KemAlgorithm::X25519MlKem768 => {
+                let pq_ciphertext = self.pq_ciphertext.as_deref().ok_or_else(|| {
+                    CryptoError::DecryptionError("Missing ML-KEM ciphertext".into())
+                })?;
+
+                let recipient_secret = StaticSecret::from(recipient_secret_bytes);
+                let x25519_secret = recipient_secret.diffie_hellman(&PublicKey::from(self.ephemeral_pub));
+                let pq_secret = pq::decapsulate(recipient_pq_secret, pq_ciphertext)?;
+
+                let kek_bytes = derive_hybrid_kek(
+                    x25519_secret.as_bytes(),
+                    &pq_secret,
+                    &self.ephemeral_pub,
+                    &self.recipient_id,
+                    pq_ciphertext,
+                );
+                unwrap_dek(kek_bytes, &self.wrapped_key)
+            }
+        }
+    }
+
+    fn seal_x25519(
+        suite: CipherSuite,
+        dek: &SymmetricKey,
+        recipient_pub_bytes: [u8; 32],
+        expiry: u64,
     ) -> Result<Self, CryptoError> {
         // 1. Generate Ephemeral Keypair for this envelope
         let ephemeral_secret = StaticSecret::random_from_rng(OsRng);
@@ -51,24 +182,19 @@ impl Envelope {
         let kek_bytes: [u8; 32] = hasher.finalize().into();
 
         // 4. Wrap the DEK using AES-KW
-        let kek = KekAes256::from(kek_bytes);
-        let wrapped_key = kek
-            .wrap_vec(dek.as_bytes())
-            .map_err(|_| CryptoError::EncryptionError("Key wrapping failed".into()))?;
+        let wrapped_key = wrap_dek(kek_bytes, dek)?;
 
         Ok(Envelope {
+            suite,
             ephemeral_pub: *ephemeral_pub.as_bytes(),
             recipient_id: recipient_pub_bytes,
             expiry,
             wrapped_key,
+            pq_ciphertext: None,
         })
     }
 
-    /// Open an envelope to recover the SymmetricKey.
-    ///
-    /// # Arguments
-    /// * `recipient_secret_bytes` - The recipient's X25519 private key.
-    pub fn open(
+    fn open_x25519(
         &self,
         recipient_secret_bytes: [u8; 32],
     ) -> Result<SymmetricKey, CryptoError> {
@@ -85,18 +211,74 @@ impl Envelope {
         let kek_bytes: [u8; 32] = hasher.finalize().into();
 
         // 4. Unwrap the DEK
-        let kek = KekAes256::from(kek_bytes);
-        let dek_bytes_vec = kek
-            .unwrap_vec(&self.wrapped_key)
-            .map_err(|_| CryptoError::DecryptionError("Key unwrapping failed".into()))?;
+        unwrap_dek(kek_bytes, &self.wrapped_key)
+    }
+}
 
-        if dek_bytes_vec.len() != KEY_SIZE {
-            return Err(CryptoError::DecryptionError("Invalid unwrapped key length".into()));
-        }
+/// Wrap the DEK under a KEK using AES-KW.
+fn wrap_dek(kek_bytes: [u8; 32], dek: &SymmetricKey) -> Result<Vec<u8>, CryptoError> {
+    KekAes256::from(kek_bytes)
+        .wrap_vec(dek.as_bytes())
+        .map_err(|_| CryptoError::EncryptionError("Key wrapping failed".into()))
+}
+
+/// Unwrap an AES-KW wrapped DEK and check its length.
+fn unwrap_dek(kek_bytes: [u8; 32], wrapped_key: &[u8]) -> Result<SymmetricKey, CryptoError> {
+    let dek_bytes_vec = KekAes256::from(kek_bytes)
+        .unwrap_vec(wrapped_key)
+        .map_err(|_| CryptoError::DecryptionError("Key unwrapping failed".into()))?;
 
-        let mut arr = [0u8; KEY_SIZE];
-        arr.copy_from_slice(&dek_bytes_vec);
-        Ok(SymmetricKey::from_bytes(arr))
+    if dek_bytes_vec.len() != KEY_SIZE {
+        return Err(CryptoError::DecryptionError("Invalid unwrapped key length".into()));
+    }
+
+    let mut arr = [0u8; KEY_SIZE];
+    arr.copy_from_slice(&dek_bytes_vec);
+    Ok(SymmetricKey::from_bytes(arr))
+}
+
+/// ML-KEM-768 key generation and encapsulation over raw byte encodings.
+#[cfg(feature = "pq-hybrid")]
+pub mod pq {
+    use crate::CryptoError;
+    use ml_kem::kem::{Decapsulate, Encapsulate};
+    use ml_kem::{Ciphertext, Encoded, EncodedSizeUser, KemCore, MlKem768};
+    use rand::rngs::OsRng;
+
+    type EncapsulationKey = <MlKem768 as KemCore>::EncapsulationKey;
+    type DecapsulationKey = <MlKem768 as KemCore>::DecapsulationKey;
+
+    /// Size of an encoded ML-KEM-768 encapsulation (public) key.
+    pub const PUBLIC_KEY_SIZE: usize = 1184;
+    /// Size of an encoded ML-KEM-768 decapsulation (secret) key.
+    pub const SECRET_KEY_SIZE: usize = 2400;
+    /// Size of an ML-KEM-768 ciphertext.
+    pub const CIPHERTEXT_SIZE: usize = 1088;
+
+    /// Generate a recipient keypair. Returns `(secret, public)`.
+    pub fn generate_keypair() -> (Vec<u8>, Vec<u8>) {
+        let (dk, ek) = MlKem768::generate(&mut OsRng);
+        (dk.as_bytes().to_vec(), ek.as_bytes().to_vec())
+    }
+
+    pub(crate) fn encapsulate(public: &[u8]) -> Result<(Vec<u8>, [u8; 32]), CryptoError> {
+        let encoded = Encoded::<EncapsulationKey>::try_from(public)
+            .map_err(|_| CryptoError::EncryptionError("Invalid ML-KEM public key length".into()))?;
+        let (ciphertext, shared) = EncapsulationKey::from_bytes(&encoded)
+            .encapsulate(&mut OsRng)
+            .map_err(|_| CryptoError::EncryptionError("ML-KEM encapsulation failed".into()))?;
+        Ok((ciphertext.to_vec(), shared.into()))
+    }
+
+    pub(crate) fn decapsulate(secret: &[u8], ciphertext: &[u8]) -> Result<[u8; 32], CryptoError> {
+        let encoded = Encoded::<DecapsulationKey>::try_from(secret)
+            .map_err(|_| CryptoError::DecryptionError("Invalid ML-KEM secret key length".into()))?;
+        let ciphertext = Ciphertext::<MlKem768>::try_from(ciphertext)
+            .map_err(|_| CryptoError::DecryptionError("Invalid ML-KEM ciphertext length".into()))?;
+        let shared = DecapsulationKey::from_bytes(&encoded)
+            .decapsulate(&ciphertext)
+            .map_err(|_| CryptoError::DecryptionError("ML-KEM decapsulation failed".into()))?;
+        Ok(shared.into())
     }
 }
 
@@ -125,4 +307,22 @@ mod tests {
 
         assert_eq!(dek.as_bytes(), recovered_dek.as_bytes());
     }
+
+    #[cfg(feature = "pq-hybrid")]
+    #[test]
+    fn test_hybrid_envelope_flow() {
+        let rec_secret = StaticSecret::random_from_rng(OsRng);
+        let rec_pub = PublicKey::from(&rec_secret);
+        let (pq_secret, pq_public) = pq::generate_keypair();
+        let dek = SymmetricKey::generate();
+
+        let envelope = Envelope::seal_hybrid(&dek, *rec_pub.as_bytes(), &pq_public, 9999999999).unwrap();
+        assert_eq!(envelope.suite, CipherSuite::HybridPq);
+
+        // The classic flow refuses rather than silently ignoring the PQ half
+        assert!(envelope.open(rec_secret.to_bytes()).is_err());
+
+        let recovered_dek = envelope.open_hybrid(rec_secret.to_bytes(), &pq_secret).unwrap();
+        assert_eq!(dek.as_bytes(), recovered_dek.as_bytes());
+    }
 }
\ No newline at end of file
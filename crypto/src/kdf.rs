@@ -4,8 +4,12 @@ use argon2::{
     },
     Argon2, Params,
 };
+use sha2::{Digest, Sha256};
 use crate::CryptoError;
 
+/// Domain separation label for the hybrid envelope KEK.
+const HYBRID_KEK_LABEL: &[u8] = b"share-envelope-x25519-mlkem768-v1";
+
 /// Derives a 32-byte recovery key from a user passphrase using Argon2id.
 ///
 /// # Parameters (as per CRYPTO_PROTOCOL_SPEC.md)
@@ -52,6 +56,29 @@ pub fn derive_recovery_key(passphrase: &str, salt: &str) -> Result<[u8; 32], Cry
     }
 }
 
+/// Derives an envelope KEK from an X25519 and an ML-KEM-768 shared secret.
+///
+/// KEK = SHA-256(label || ss_x25519 || ss_mlkem || ephemeral_pub || recipient_pub || mlkem_ct)
+///
+/// Binding the public transcript ties the KEK to this exact envelope, and the
+/// result stays secret as long as either shared secret does.
+pub fn derive_hybrid_kek(
+    x25519_secret: &[u8; 32],
+    mlkem_secret: &[u8; 32],
+    ephemeral_pub: &[u8; 32],
+    recipient_pub: &[u8; 32],
+    mlkem_ciphertext: &[u8],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(HYBRID_KEK_LABEL);
+    hasher.update(x25519_secret);
+    hasher.update(mlkem_secret);
+    hasher.update(ephemeral_pub);
+    hasher.update(recipient_pub);
+    hasher.update(mlkem_ciphertext);
+    hasher.finalize().into()
+}
+
 /// Generates a random salt for new account creation.
 pub fn generate_salt() -> String {
     SaltString::generate(&mut OsRng).as_str().to_string()
@@ -85,4 +112,13 @@ mod tests {
 
         assert_ne!(key1, key2);
     }
+
+    #[test]
+    fn test_hybrid_kek_depends_on_both_secrets() {
+        let base = derive_hybrid_kek(&[1; 32], &[2; 32], &[3; 32], &[4; 32], &[5; 8]);
+
+        assert_ne!(base, derive_hybrid_kek(&[9; 32], &[2; 32], &[3; 32], &[4; 32], &[5; 8]));
+        assert_ne!(base, derive_hybrid_kek(&[1; 32], &[9; 32], &[3; 32], &[4; 32], &[5; 8]));
+        assert_ne!(base, derive_hybrid_kek(&[1; 32], &[2; 32], &[3; 32], &[4; 32], &[9; 8]));
+    }
 }
\ No newline at end of file
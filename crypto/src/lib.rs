@@ -5,13 +5,19 @@ pub mod hashing;
 pub mod encryption;
 pub mod kdf;
 pub mod signatures;
+pub mod address;
 pub mod threshold;
 pub mod envelopes;
+pub mod batch;
+pub mod suite;
+pub mod wire;
+pub mod merkle;
 
 // Re-exports for easier access
 pub use hashing::ContentHash;
 pub use encryption::Ciphertext;
 pub use signatures::IdentityKey;
+pub use suite::CipherSuite;
 
 /// Common error types for cryptographic operations
 #[derive(Debug, thiserror::Error)]
@@ -22,6 +28,20 @@ pub enum CryptoError {
     DecryptionError(String),
     #[error("Invalid signature")]
     InvalidSignature,
+    #[error("Invalid or weak public key")]
+    InvalidKey,
+    #[error("Identity is for network '{found}', expected '{expected}'")]
+    WrongNetwork { expected: String, found: String },
+    #[error("Batch verification failed for items {0:?}")]
+    BatchVerificationError(Vec<usize>),
+    #[error("Unsupported cipher suite: {0:#06x}")]
+    UnsupportedSuite(u16),
+    #[error("Cipher suite {0:#06x} is deprecated for new content")]
+    DeprecatedSuite(u16),
+    #[error("Encoding failure: {0}")]
+    EncodingError(String),
+    #[error("Decoding failure: {0}")]
+    DecodingError(String),
     #[error("Key derivation failed")]
     KdfError,
     #[error("Threshold signature aggregation failed")]
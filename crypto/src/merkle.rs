@@ -0,0 +1,124 @@
+//! Binary BLAKE3 Merkle tree for committing to ordered lists (e.g. ballots).
+//!
+//! Leaves are hashed as `0x00 || data` and nodes as `0x01 || left || right`,
+//! so a leaf can never be passed off as a node. An unpaired node is carried up
+//! a level unchanged rather than duplicated.
+
+use crate::ContentHash;
+use serde::{Deserialize, Serialize};
+
+const LEAF: u8 = 0x00;
+const NODE: u8 = 0x01;
+
+pub fn leaf_hash(data: &[u8]) -> ContentHash {
+    ContentHash::hash(&[&[LEAF], data].concat())
+}
+
+fn node_hash(left: &ContentHash, right: &ContentHash) -> ContentHash {
+    ContentHash::hash(&[&[NODE][..], left.as_bytes(), right.as_bytes()].concat())
+}
+
+pub struct MerkleTree {
+    /// `levels[0]` are the leaf hashes, the last level is the root.
+    levels: Vec<Vec<ContentHash>>,
+}
+
+impl MerkleTree {
+    pub fn new<L: AsRef<[u8]>>(leaves: impl IntoIterator<Item = L>) -> Self {
+        let mut levels = vec![leaves.into_iter().map(|l| leaf_hash(l.as_ref())).collect::<Vec<_>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        MerkleTree { levels }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// The root; for an empty tree, the hash of the empty string.
+    pub fn root(&self) -> ContentHash {
+        self.levels[self.levels.len() - 1].first().copied().unwrap_or_else(|| ContentHash::hash(&[]))
+    }
+
+    /// Inclusion proof for the leaf at `index`.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.leaf_count() {
+            return None;
+        }
+        let mut siblings = Vec::new();
+        let mut i = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = i ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            i /= 2;
+        }
+        Some(MerkleProof { index: index as u64, leaf_count: self.leaf_count() as u64, siblings })
+    }
+}
+
+/// Proof that a leaf sits at `index` in a tree of `leaf_count` leaves.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub index: u64,
+    pub leaf_count: u64,
+    pub siblings: Vec<ContentHash>,
+}
+
+impl MerkleProof {
+    pub fn verify(&self, root: &ContentHash, leaf: &[u8]) -> bool {
+        if self.index >= self.leaf_count {
+            return false;
+        }
+        let mut hash = leaf_hash(leaf);
+        let mut siblings = self.siblings.iter();
+        let (mut index, mut width) = (self.index, self.leaf_count);
+        while width > 1 {
+            if index % 2 == 1 {
+                let Some(left) = siblings.next() else { return false };
+                hash = node_hash(left, &hash);
+            } else if index + 1 < width {
+                let Some(right) = siblings.next() else { return false };
+                hash = node_hash(&hash, right);
+            }
+            index /= 2;
+            width = width.div_ceil(2);
+        }
+        siblings.next().is_none() && hash == *root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_leaf_proves_against_root() {
+        for count in 1..=9u8 {
+            let leaves: Vec<Vec<u8>> = (0..count).map(|i| vec![i]).collect();
+            let tree = MerkleTree::new(&leaves);
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                assert!(proof.verify(&tree.root(), leaf));
+                assert!(!proof.verify(&tree.root(), b"other"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_leaf_root_is_leaf_hash() {
+        let tree = MerkleTree::new([b"only"]);
+        assert_eq!(tree.root(), leaf_hash(b"only"));
+        assert_eq!(tree.proof(1), None);
+    }
+}
@@ -1,4 +1,5 @@
-use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey, Signature};
+use curve25519_dalek::{edwards::CompressedEdwardsY, scalar::Scalar};
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey, Signature};
 use serde::{Deserialize, Serialize, Serializer, Deserializer};
 use std::fmt;
 use crate::CryptoError;
@@ -9,24 +10,81 @@ use crate::CryptoError;
 pub struct IdentityKey(VerifyingKey);
 
 impl IdentityKey {
+    /// Parse a public key, rejecting weak keys.
+    ///
+    /// The encoding must be canonical and the point must lie in the prime-order
+    /// subgroup; small-order and mixed-order keys are refused.
     pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, CryptoError> {
+        if !is_canonical_prime_order(bytes) {
+            return Err(CryptoError::InvalidKey);
+        }
         VerifyingKey::from_bytes(bytes)
             .map(IdentityKey)
-            .map_err(|_| CryptoError::InvalidSignature)
+            .map_err(|_| CryptoError::InvalidKey)
     }
 
     pub fn as_bytes(&self) -> [u8; 32] {
         self.0.to_bytes()
     }
 
-    /// Verify a signature against a message.
+    /// Verify a signature against a message using strict (non-malleable) rules.
+    ///
+    /// On top of `verify_strict` (canonical `S`, no small-order `R`), `R` must be
+    /// canonically encoded and torsion-free, so each accepted signature has
+    /// exactly one valid encoding. See [`CanonicalSignature`].
     pub fn verify(&self, message: &[u8], signature_bytes: &[u8; 64]) -> Result<(), CryptoError> {
-        let signature = Signature::from_bytes(signature_bytes);
-        self.0.verify(message, &signature)
+        let signature = CanonicalSignature::from_bytes(signature_bytes)?;
+        self.0
+            .verify_strict(message, &Signature::from_bytes(&signature.0))
             .map_err(|_| CryptoError::InvalidSignature)
     }
 }
 
+/// The canonical encoding of an Ed25519 signature: `R || S` (64 bytes).
+///
+/// * `R` is a canonically compressed Edwards point in the prime-order subgroup.
+/// * `S` is a little-endian scalar strictly below the group order `l`.
+///
+/// Any other encoding of the same signature is rejected by [`IdentityKey::verify`],
+/// so a third party cannot re-encode a vote to have it counted twice. The signer
+/// can still produce several distinct valid signatures over one message, so the
+/// runtime must deduplicate on `(signer, message)` and store this form only as
+/// evidence.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CanonicalSignature([u8; 64]);
+
+impl CanonicalSignature {
+    /// Validate the encoding of raw signature bytes (does not check validity).
+    pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self, CryptoError> {
+        let mut r_bytes = [0u8; 32];
+        let mut s_bytes = [0u8; 32];
+        r_bytes.copy_from_slice(&bytes[..32]);
+        s_bytes.copy_from_slice(&bytes[32..]);
+
+        let s_canonical: bool = Scalar::from_canonical_bytes(s_bytes).is_some().into();
+        if !s_canonical || !is_canonical_prime_order(&r_bytes) {
+            return Err(CryptoError::InvalidSignature);
+        }
+        Ok(Self(*bytes))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        self.0
+    }
+}
+
+/// True if `bytes` canonically encodes a non-identity point of prime order.
+fn is_canonical_prime_order(bytes: &[u8; 32]) -> bool {
+    match CompressedEdwardsY(*bytes).decompress() {
+        Some(point) => {
+            point.compress().as_bytes() == bytes
+                && !point.is_small_order()
+                && point.is_torsion_free()
+        }
+        None => false,
+    }
+}
+
 // Custom Serde implementation to serialize as hex strings (friendly for JSON manifests)
 impl Serialize for IdentityKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -107,4 +165,38 @@ mod tests {
         let deserialized: IdentityKey = serde_json::from_str(&json).unwrap();
         assert_eq!(public, deserialized);
     }
+
+    #[test]
+    fn test_rejects_weak_key() {
+        // The identity point (0, 1) has small order
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        assert!(IdentityKey::from_bytes(&identity).is_err());
+    }
+
+    #[test]
+    fn test_rejects_malleated_signature() {
+        let secret = UserSecret::generate();
+        let public = secret.public_key();
+        let message = b"Vote: yes";
+        let sig = secret.sign(message);
+
+        // S + l encodes the same scalar; a non-canonical S must never verify
+        // (ed25519-dalek 2 rejects it on the lenient path too)
+        const L: [u8; 32] = [
+            0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9,
+            0xde, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x10,
+        ];
+        let mut malleated = sig;
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let sum = malleated[32 + i] as u16 + L[i] as u16 + carry;
+            malleated[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+
+        assert!(public.verify(message, &sig).is_ok());
+        assert!(public.verify(message, &malleated).is_err());
+    }
 }
\ No newline at end of file
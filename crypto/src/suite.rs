@@ -0,0 +1,205 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use crate::CryptoError;
+
+/// Authenticated encryption algorithms, by stable wire identifier.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(u8)]
+pub enum AeadAlgorithm {
+    XChaCha20Poly1305 = 0x01,
+}
+
+/// Key derivation functions, by stable wire identifier.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(u8)]
+pub enum KdfAlgorithm {
+    /// Argon2id with t=2, m=64MB, p=4 (see `kdf::derive_recovery_key`).
+    Argon2id = 0x01,
+}
+
+/// Key encapsulation used by envelopes, by stable wire identifier.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(u8)]
+pub enum KemAlgorithm {
+    /// Ephemeral X25519, SHA-256 of the shared secret as KEK, AES-KW-256.
+    X25519Sha256AesKw = 0x01,
+    /// Ephemeral X25519 plus ML-KEM-768, both secrets combined by
+    /// `kdf::derive_hybrid_kek`, AES-KW-256. Requires the `pq-hybrid` feature.
+    X25519MlKem768 = 0x02,
+}
+
+/// Content hash functions, by stable wire identifier.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(u8)]
+pub enum HashAlgorithm {
+    Blake3 = 0x01,
+}
+
+/// Lifecycle of a cipher suite.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SuiteStatus {
+    /// May be used to produce new ciphertexts and envelopes.
+    Active,
+    /// May only be used to read historical content. New content must use an
+    /// active suite; existing ciphertexts keep their ID and remain decryptable.
+    Deprecated,
+}
+
+/// A named combination of primitives, identified on the wire by a `u16`.
+///
+/// IDs are permanent: a suite is never renumbered or removed, only deprecated,
+/// so content encrypted years ago can always be dispatched to the right code.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(u16)]
+pub enum CipherSuite {
+    /// XChaCha20-Poly1305 / Argon2id / X25519+AES-KW / BLAKE3
+    /// (CRYPTO_PROTOCOL_SPEC.md §2).
+    Classic = 0x0001,
+    /// Classic, with the envelope KEM replaced by X25519 + ML-KEM-768.
+    /// Opt-in via `Envelope::seal_hybrid`; not the default for new content.
+    HybridPq = 0x0002,
+}
+
+impl CipherSuite {
+    /// Every registered suite, in ID order.
+    pub const REGISTRY: &'static [CipherSuite] = &[CipherSuite::Classic, CipherSuite::HybridPq];
+
+    /// The suite used for new content.
+    pub const CURRENT: CipherSuite = CipherSuite::Classic;
+
+    /// The suite assumed for data written before suite IDs existed.
+    pub fn legacy() -> Self {
+        CipherSuite::Classic
+    }
+
+    /// Look up a suite by its wire identifier.
+    pub fn from_id(id: u16) -> Result<Self, CryptoError> {
+        Self::REGISTRY
+            .iter()
+            .copied()
+            .find(|suite| suite.id() == id)
+            .ok_or(CryptoError::UnsupportedSuite(id))
+    }
+
+    /// Look up a suite by the legacy manifest `algo` string.
+    /// Unknown names are reported as suite ID 0, which is never assigned.
+    pub fn from_legacy_name(name: &str) -> Result<Self, CryptoError> {
+        match name {
+            "XChaCha20Poly1305" => Ok(CipherSuite::Classic),
+            _ => Err(CryptoError::UnsupportedSuite(0)),
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        *self as u16
+    }
+
+    pub fn status(&self) -> SuiteStatus {
+        match self {
+            CipherSuite::Classic => SuiteStatus::Active,
+            CipherSuite::HybridPq => SuiteStatus::Active,
+        }
+    }
+
+    pub fn aead(&self) -> AeadAlgorithm {
+        match self {
+            CipherSuite::Classic => AeadAlgorithm::XChaCha20Poly1305,
+            CipherSuite::HybridPq => AeadAlgorithm::XChaCha20Poly1305,
+        }
+    }
+
+    pub fn kdf(&self) -> KdfAlgorithm {
+        match self {
+            CipherSuite::Classic => KdfAlgorithm::Argon2id,
+            CipherSuite::HybridPq => KdfAlgorithm::Argon2id,
+        }
+    }
+
+    pub fn kem(&self) -> KemAlgorithm {
+        match self {
+            CipherSuite::Classic => KemAlgorithm::X25519Sha256AesKw,
+            CipherSuite::HybridPq => KemAlgorithm::X25519MlKem768,
+        }
+    }
+
+    pub fn hash(&self) -> HashAlgorithm {
+        match self {
+            CipherSuite::Classic => HashAlgorithm::Blake3,
+            CipherSuite::HybridPq => HashAlgorithm::Blake3,
+        }
+    }
+
+    /// Fails unless this suite may be used to produce new content.
+    pub fn ensure_active(&self) -> Result<(), CryptoError> {
+        match self.status() {
+            SuiteStatus::Active => Ok(()),
+            SuiteStatus::Deprecated => Err(CryptoError::DeprecatedSuite(self.id())),
+        }
+    }
+}
+
+impl Default for CipherSuite {
+    fn default() -> Self {
+        Self::legacy()
+    }
+}
+
+impl fmt::Display for CipherSuite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}({:#06x})", self, self.id())
+    }
+}
+
+// Serialize as the numeric ID; accept the legacy `algo` string when reading old manifests.
+impl Serialize for CipherSuite {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u16(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for CipherSuite {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Id(u16),
+            Name(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Id(id) => CipherSuite::from_id(id),
+            Repr::Name(name) => CipherSuite::from_legacy_name(&name),
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_ids_are_stable() {
+        assert_eq!(CipherSuite::Classic.id(), 0x0001);
+        for suite in CipherSuite::REGISTRY {
+            assert_eq!(CipherSuite::from_id(suite.id()).unwrap(), *suite);
+        }
+        assert!(CipherSuite::from_id(0xFFFF).is_err());
+    }
+
+    #[test]
+    fn test_serde_accepts_id_and_legacy_name() {
+        let json = serde_json::to_string(&CipherSuite::Classic).unwrap();
+        assert_eq!(json, "1");
+
+        let from_id: CipherSuite = serde_json::from_str("1").unwrap();
+        let from_name: CipherSuite = serde_json::from_str("\"XChaCha20Poly1305\"").unwrap();
+        assert_eq!(from_id, from_name);
+    }
+}
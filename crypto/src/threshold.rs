@@ -5,30 +5,62 @@ use serde::{Deserialize, Serialize};
 use crate::CryptoError;
 
 /// A wrapper around the Council's aggregated public key set.
+///
+/// The canonical byte form is computed once at construction, so encoders
+/// (`wire`, SCALE) write bytes the key set already holds and cannot fail.
 #[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct CouncilKeySet(PublicKeySet);
+#[serde(try_from = "PublicKeySet", into = "PublicKeySet")]
+pub struct CouncilKeySet {
+    keys: PublicKeySet,
+    bytes: Vec<u8>,
+}
+
+/// Size in bytes of a BLS12-381 signature or signature share.
+pub const SIGNATURE_SIZE: usize = 96;
 
 impl CouncilKeySet {
     /// Create from raw bytes (usually from on-chain storage).
     /// Note: Implementation depends on serialization format of threshold_crypto.
+    /// For transport use the versioned form in `wire`.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
-        bincode::deserialize(bytes)
-            .map(CouncilKeySet)
-            .map_err(|_| CryptoError::ThresholdError)
+        let keys: PublicKeySet = bincode::deserialize(bytes)
+            .map_err(|e| CryptoError::DecodingError(format!("Invalid council key set: {}", e)))?;
+        Self::try_from(keys)
+    }
+
+    /// The canonical encoding, as accepted by `from_bytes`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        bincode::serialize(&self.0).unwrap_or_default()
+        self.bytes.clone()
     }
 
     /// Verify a fully aggregated signature from the council.
     pub fn verify(&self, message: &[u8], signature: &CombinedSignature) -> bool {
-        self.0.public_key().verify(&signature.0, message)
+        self.keys.public_key().verify(&signature.0, message)
     }
 
     /// The threshold required to reconstruct a signature (e.g., 7 out of 10).
     pub fn threshold(&self) -> usize {
-        self.0.threshold()
+        self.keys.threshold()
+    }
+}
+
+impl TryFrom<PublicKeySet> for CouncilKeySet {
+    type Error = CryptoError;
+
+    fn try_from(keys: PublicKeySet) -> Result<Self, CryptoError> {
+        let bytes = bincode::serialize(&keys)
+            .map_err(|e| CryptoError::EncodingError(format!("Council key set: {}", e)))?;
+        Ok(CouncilKeySet { keys, bytes })
+    }
+}
+
+impl From<CouncilKeySet> for PublicKeySet {
+    fn from(set: CouncilKeySet) -> Self {
+        set.keys
     }
 }
 
@@ -46,11 +78,33 @@ impl MemberSecret {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PartialVote(SignatureShare);
 
+impl PartialVote {
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_SIZE]) -> Result<Self, CryptoError> {
+        SignatureShare::from_bytes(*bytes)
+            .map(PartialVote)
+            .map_err(|_| CryptoError::DecodingError("Invalid signature share".into()))
+    }
+
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_SIZE] {
+        self.0.to_bytes()
+    }
+}
+
 /// The final aggregated signature representing the Council's will.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CombinedSignature(threshold_crypto::Signature);
 
 impl CombinedSignature {
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_SIZE]) -> Result<Self, CryptoError> {
+        threshold_crypto::Signature::from_bytes(*bytes)
+            .map(CombinedSignature)
+            .map_err(|_| CryptoError::DecodingError("Invalid combined signature".into()))
+    }
+
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_SIZE] {
+        self.0.to_bytes()
+    }
+
     /// Aggregate partial votes into a final signature.
     pub fn combine(
         key_set: &CouncilKeySet,
@@ -62,7 +116,7 @@ impl CombinedSignature {
             .map(|(idx, vote)| (*idx, &vote.0))
             .collect();
 
-        key_set.0
+        key_set.keys
             .combine_signatures(internal_shares)
             .map(CombinedSignature)
             .map_err(|_| CryptoError::ThresholdError)
@@ -80,7 +134,7 @@ mod tests {
         let mut rng = rand::thread_rng();
         // 1. DKG Simulation: Create a set for 5 members, threshold 3
         let sk_set = SecretKeySet::random(3, &mut rng);
-        let pk_set = CouncilKeySet(sk_set.public_keys());
+        let pk_set = CouncilKeySet::try_from(sk_set.public_keys()).unwrap();
 
         let msg = b"Ratify Fork v2.0";
 
@@ -0,0 +1,531 @@
+//! Compact, versioned binary encoding for every public crypto type.
+//!
+//! Layout (see CRYPTO_PROTOCOL_SPEC.md §7):
+//!
+//! ```text
+//! version: u8 || type tag: u8 || fields...
+//! ```
+//!
+//! * Integers are little-endian (`u16`, `u64`).
+//! * Fixed-size values (keys, hashes, signatures) are written raw.
+//! * Variable-length bytes are a `u32` length prefix followed by the data.
+//! * Optional bytes are a `0x00` (absent) or `0x01` (present) flag, then bytes.
+//!
+//! Decoding rejects unknown versions, mismatched tags, truncated input and
+//! trailing bytes, so every value has exactly one encoding.
+
+use crate::encryption::Ciphertext;
+use crate::envelopes::Envelope;
+use crate::hashing::ContentHash;
+use crate::signatures::{CanonicalSignature, IdentityKey};
+use crate::suite::CipherSuite;
+use crate::threshold::{CombinedSignature, CouncilKeySet, PartialVote, SIGNATURE_SIZE};
+use crate::CryptoError;
+
+/// Current wire format version.
+pub const WIRE_VERSION: u8 = 1;
+
+/// Type tags, one per encodable type. Never reuse a retired tag.
+pub mod tag {
+    pub const CONTENT_HASH: u8 = 0x01;
+    pub const IDENTITY_KEY: u8 = 0x02;
+    pub const SIGNATURE: u8 = 0x03;
+    pub const CIPHER_SUITE: u8 = 0x04;
+    pub const CIPHERTEXT: u8 = 0x05;
+    pub const ENVELOPE: u8 = 0x06;
+    pub const COUNCIL_KEY_SET: u8 = 0x07;
+    pub const PARTIAL_VOTE: u8 = 0x08;
+    pub const COMBINED_SIGNATURE: u8 = 0x09;
+    /// `core::summary::Summary`.
+    pub const SUMMARY: u8 = 0x0A;
+    /// `core::manifest::Manifest`.
+    pub const MANIFEST: u8 = 0x0B;
+    /// `core::fork::ForkDescriptor`.
+    pub const FORK_DESCRIPTOR: u8 = 0x0C;
+}
+
+/// A type with a canonical wire encoding.
+pub trait WireCodec: Sized {
+    /// The type tag written after the version byte.
+    const TAG: u8;
+
+    /// Write this value's fields (without the header).
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError>;
+
+    /// Read this value's fields (without the header).
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError>;
+
+    /// Encode with the version/tag header.
+    fn to_wire(&self) -> Result<Vec<u8>, CryptoError> {
+        let mut writer = WireWriter::default();
+        writer.u8(WIRE_VERSION);
+        writer.u8(Self::TAG);
+        self.encode_fields(&mut writer)?;
+        Ok(writer.into_bytes())
+    }
+
+    /// Decode a complete, header-prefixed value. Trailing bytes are an error.
+    fn from_wire(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let mut reader = WireReader::new(bytes);
+
+        let version = reader.u8()?;
+        if version != WIRE_VERSION {
+            return Err(CryptoError::DecodingError(format!("Unsupported wire version {}", version)));
+        }
+        let tag = reader.u8()?;
+        if tag != Self::TAG {
+            return Err(CryptoError::DecodingError(format!(
+                "Expected type tag {:#04x}, found {:#04x}",
+                Self::TAG,
+                tag
+            )));
+        }
+
+        let value = Self::decode_fields(&mut reader)?;
+        reader.finish()?;
+        Ok(value)
+    }
+}
+
+/// Appends wire primitives to a buffer.
+#[derive(Default)]
+pub struct WireWriter {
+    buf: Vec<u8>,
+}
+
+impl WireWriter {
+    pub fn u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Write a fixed-size value with no length prefix.
+    pub fn raw(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Write variable-length bytes with a `u32` length prefix.
+    pub fn bytes(&mut self, bytes: &[u8]) -> Result<(), CryptoError> {
+        let len = u32::try_from(bytes.len())
+            .map_err(|_| CryptoError::EncodingError("Field exceeds 4 GiB".into()))?;
+        self.buf.extend_from_slice(&len.to_le_bytes());
+        self.buf.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    pub fn optional_bytes(&mut self, bytes: Option<&[u8]>) -> Result<(), CryptoError> {
+        match bytes {
+            None => {
+                self.u8(0);
+                Ok(())
+            }
+            Some(bytes) => {
+                self.u8(1);
+                self.bytes(bytes)
+            }
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+/// Reads wire primitives from a buffer, failing on truncation.
+pub struct WireReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> WireReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CryptoError> {
+        if self.data.len() < len {
+            return Err(CryptoError::DecodingError("Unexpected end of input".into()));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, CryptoError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16, CryptoError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, CryptoError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    /// Read a fixed-size value with no length prefix.
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], CryptoError> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    /// Read `u32`-length-prefixed bytes.
+    pub fn bytes(&mut self) -> Result<Vec<u8>, CryptoError> {
+        let len = u32::from_le_bytes(self.array()?) as usize;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    pub fn optional_bytes(&mut self) -> Result<Option<Vec<u8>>, CryptoError> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => self.bytes().map(Some),
+            flag => Err(CryptoError::DecodingError(format!("Invalid option flag {}", flag))),
+        }
+    }
+
+    /// Fail if any input remains.
+    pub fn finish(self) -> Result<(), CryptoError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(CryptoError::DecodingError(format!("{} trailing bytes", self.data.len())))
+        }
+    }
+}
+
+impl WireCodec for ContentHash {
+    const TAG: u8 = tag::CONTENT_HASH;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.raw(self.as_bytes());
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        Ok(ContentHash::from_bytes(reader.array()?))
+    }
+}
+
+impl WireCodec for IdentityKey {
+    const TAG: u8 = tag::IDENTITY_KEY;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.raw(&self.as_bytes());
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        IdentityKey::from_bytes(&reader.array()?)
+    }
+}
+
+impl WireCodec for CanonicalSignature {
+    const TAG: u8 = tag::SIGNATURE;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.raw(&self.to_bytes());
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        CanonicalSignature::from_bytes(&reader.array()?)
+    }
+}
+
+impl WireCodec for CipherSuite {
+    const TAG: u8 = tag::CIPHER_SUITE;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.u16(self.id());
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        CipherSuite::from_id(reader.u16()?)
+    }
+}
+
+impl WireCodec for Ciphertext {
+    const TAG: u8 = tag::CIPHERTEXT;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.u16(self.suite.id());
+        writer.bytes(&self.nonce)?;
+        writer.bytes(&self.data)
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        Ok(Ciphertext {
+            suite: CipherSuite::from_id(reader.u16()?)?,
+            nonce: reader.bytes()?,
+            data: reader.bytes()?,
+        })
+    }
+}
+
+impl WireCodec for Envelope {
+    const TAG: u8 = tag::ENVELOPE;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.u16(self.suite.id());
+        writer.raw(&self.ephemeral_pub);
+        writer.raw(&self.recipient_id);
+        writer.u64(self.expiry);
+        writer.bytes(&self.wrapped_key)?;
+        writer.optional_bytes(self.pq_ciphertext.as_deref())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        Ok(Envelope {
+            suite: CipherSuite::from_id(reader.u16()?)?,
+            ephemeral_pub: reader.array()?,
+            recipient_id: reader.array()?,
+            expiry: reader.u64()?,
+            wrapped_key: reader.bytes()?,
+            pq_ciphertext: reader.optional_bytes()?,
+        })
+    }
+}
+
+impl WireCodec for CouncilKeySet {
+    const TAG: u8 = tag::COUNCIL_KEY_SET;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.bytes(self.as_bytes())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        CouncilKeySet::from_bytes(&reader.bytes()?)
+    }
+}
+
+impl WireCodec for PartialVote {
+    const TAG: u8 = tag::PARTIAL_VOTE;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.raw(&self.to_bytes());
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        PartialVote::from_bytes(&reader.array::<SIGNATURE_SIZE>()?)
+    }
+}
+
+impl WireCodec for CombinedSignature {
+    const TAG: u8 = tag::COMBINED_SIGNATURE;
+
+    fn encode_fields(&self, writer: &mut WireWriter) -> Result<(), CryptoError> {
+        writer.raw(&self.to_bytes());
+        Ok(())
+    }
+
+    fn decode_fields(reader: &mut WireReader<'_>) -> Result<Self, CryptoError> {
+        CombinedSignature::from_bytes(&reader.array::<SIGNATURE_SIZE>()?)
+    }
+}
+
+/// SCALE codec impls for runtime storage and extrinsics.
+///
+/// These follow SCALE's own conventions (compact length prefixes, no header)
+/// rather than the wire layout above, and validate on decode exactly as
+/// `from_wire` does.
+#[cfg(feature = "scale")]
+mod scale {
+    use super::*;
+    use codec::{Decode, Encode, Error, Input, Output};
+
+    macro_rules! impl_scale_fixed {
+        ($ty:ty, $len:expr, $to:expr, $from:expr) => {
+            impl Encode for $ty {
+                fn size_hint(&self) -> usize {
+                    $len
+                }
+
+                fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+                    let bytes: [u8; $len] = $to(self);
+                    bytes.encode_to(dest)
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                    let bytes = <[u8; $len]>::decode(input)?;
+                    $from(&bytes).map_err(|_| Error::from(concat!("invalid ", stringify!($ty))))
+                }
+            }
+        };
+    }
+
+    impl_scale_fixed!(ContentHash, 32, |h: &ContentHash| *h.as_bytes(), |b: &[u8; 32]| {
+        Ok::<_, CryptoError>(ContentHash::from_bytes(*b))
+    });
+    impl_scale_fixed!(IdentityKey, 32, |k: &IdentityKey| k.as_bytes(), IdentityKey::from_bytes);
+    impl_scale_fixed!(CanonicalSignature, 64, |s: &CanonicalSignature| s.to_bytes(), CanonicalSignature::from_bytes);
+    impl_scale_fixed!(PartialVote, 96, |v: &PartialVote| v.to_bytes(), PartialVote::from_bytes);
+    impl_scale_fixed!(CombinedSignature, 96, |s: &CombinedSignature| s.to_bytes(), CombinedSignature::from_bytes);
+
+    impl Encode for CipherSuite {
+        fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+            self.id().encode_to(dest)
+        }
+    }
+
+    impl Decode for CipherSuite {
+        fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+            CipherSuite::from_id(u16::decode(input)?).map_err(|_| Error::from("unknown cipher suite"))
+        }
+    }
+
+    impl Encode for Ciphertext {
+        fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+            (self.suite, &self.nonce, &self.data).encode_to(dest)
+        }
+    }
+
+    impl Decode for Ciphertext {
+        fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+            let (suite, nonce, data) = <(CipherSuite, Vec<u8>, Vec<u8>)>::decode(input)?;
+            Ok(Ciphertext { suite, nonce, data })
+        }
+    }
+
+    impl Encode for Envelope {
+        fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+            (
+                self.suite,
+                self.ephemeral_pub,
+                self.recipient_id,
+                self.expiry,
+                &self.wrapped_key,
+                &self.pq_ciphertext,
+            )
+                .encode_to(dest)
+        }
+    }
+
+    impl Decode for Envelope {
+        fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+            let (suite, ephemeral_pub, recipient_id, expiry, wrapped_key, pq_ciphertext) =
+                <(CipherSuite, [u8; 32], [u8; 32], u64, Vec<u8>, Option<Vec<u8>>)>::decode(input)?;
+            Ok(Envelope { suite, ephemeral_pub, recipient_id, expiry, wrapped_key, pq_ciphertext })
+        }
+    }
+
+    impl Encode for CouncilKeySet {
+        fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+            self.as_bytes().encode_to(dest)
+        }
+    }
+
+    impl Decode for CouncilKeySet {
+        fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+            CouncilKeySet::from_bytes(&Vec::<u8>::decode(input)?)
+                .map_err(|_| Error::from("invalid CouncilKeySet"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signatures::UserSecret;
+    use proptest::prelude::*;
+
+    fn suite() -> impl Strategy<Value = CipherSuite> {
+        proptest::sample::select(CipherSuite::REGISTRY)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_ciphertext_roundtrip(
+            suite in suite(),
+            nonce in proptest::collection::vec(any::<u8>(), 0..64),
+            data in proptest::collection::vec(any::<u8>(), 0..512),
+        ) {
+            let ciphertext = Ciphertext { suite, nonce, data };
+            let decoded = Ciphertext::from_wire(&ciphertext.to_wire().unwrap()).unwrap();
+            prop_assert_eq!(decoded.suite, ciphertext.suite);
+            prop_assert_eq!(decoded.nonce, ciphertext.nonce);
+            prop_assert_eq!(decoded.data, ciphertext.data);
+        }
+
+        #[test]
+        fn prop_envelope_roundtrip(
+            suite in suite(),
+            ephemeral_pub in any::<[u8; 32]>(),
+            recipient_id in any::<[u8; 32]>(),
+            expiry in any::<u64>(),
+            wrapped_key in proptest::collection::vec(any::<u8>(), 0..64),
+            pq_ciphertext in proptest::option::of(proptest::collection::vec(any::<u8>(), 0..128)),
+        ) {
+            let envelope = Envelope { suite, ephemeral_pub, recipient_id, expiry, wrapped_key, pq_ciphertext };
+            let bytes = envelope.to_wire().unwrap();
+            let decoded = Envelope::from_wire(&bytes).unwrap();
+            prop_assert_eq!(decoded.to_wire().unwrap(), bytes);
+        }
+
+        #[test]
+        fn prop_content_hash_roundtrip(data in proptest::collection::vec(any::<u8>(), 0..256)) {
+            let hash = ContentHash::hash(&data);
+            prop_assert_eq!(ContentHash::from_wire(&hash.to_wire().unwrap()).unwrap(), hash);
+        }
+
+        #[test]
+        fn prop_truncation_is_rejected(data in proptest::collection::vec(any::<u8>(), 1..64), cut in any::<prop::sample::Index>()) {
+            let ciphertext = Ciphertext { suite: CipherSuite::CURRENT, nonce: vec![0; 24], data };
+            let bytes = ciphertext.to_wire().unwrap();
+            let cut = cut.index(bytes.len());
+            prop_assert!(Ciphertext::from_wire(&bytes[..cut]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_identity_and_signature_roundtrip() {
+        let secret = UserSecret::generate();
+        let key = secret.public_key();
+        let signature = CanonicalSignature::from_bytes(&secret.sign(b"manifest")).unwrap();
+
+        assert_eq!(IdentityKey::from_wire(&key.to_wire().unwrap()).unwrap(), key);
+        assert_eq!(CanonicalSignature::from_wire(&signature.to_wire().unwrap()).unwrap(), signature);
+    }
+
+    #[test]
+    fn test_rejects_wrong_tag_and_trailing_bytes() {
+        let hash = ContentHash::hash(b"x");
+        let mut bytes = hash.to_wire().unwrap();
+
+        // A content hash is not an identity key, even though both are 32 bytes
+        assert!(IdentityKey::from_wire(&bytes).is_err());
+
+        bytes.push(0);
+        assert!(ContentHash::from_wire(&bytes).is_err());
+    }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn test_scale_roundtrip() {
+        use codec::{Decode, Encode};
+
+        let envelope = Envelope {
+            suite: CipherSuite::CURRENT,
+            ephemeral_pub: [1; 32],
+            recipient_id: [2; 32],
+            expiry: 9999999999,
+            wrapped_key: vec![3; 40],
+            pq_ciphertext: None,
+        };
+        let decoded = Envelope::decode(&mut &envelope.encode()[..]).unwrap();
+        assert_eq!(decoded.to_wire().unwrap(), envelope.to_wire().unwrap());
+
+        let key = UserSecret::generate().public_key();
+        assert_eq!(IdentityKey::decode(&mut &key.encode()[..]).unwrap(), key);
+    }
+}
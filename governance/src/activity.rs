@@ -0,0 +1,150 @@
+//! Activity index: when each identity last committed an on-chain transaction,
+//! and the region it declared.
+//!
+//! "Active" means active within `ACTIVITY_WINDOW_DAYS` (Art. III §2 and §4).
+
+use crate::charter::{ACTIVITY_WINDOW_DAYS, SECONDS_PER_DAY};
+use crate::council::GeoRegion;
+use crate::proposal::Timestamp;
+use alloc::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+pub const ACTIVITY_WINDOW_SECONDS: u64 = ACTIVITY_WINDOW_DAYS as u64 * SECONDS_PER_DAY;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct ActivityRecord {
+    pub last_active: Timestamp,
+    /// Start of the current run: each transaction since came within the
+    /// window of the one before, so the identity has been active throughout.
+    pub active_since: Timestamp,
+    /// Last activity of the previous run, if any.
+    pub previous_run_end: Option<Timestamp>,
+    /// Self-declared; `None` until the identity declares one.
+    pub region: Option<GeoRegion>,
+}
+
+impl ActivityRecord {
+    /// A record of a first transaction at `at`.
+    pub fn new(at: Timestamp) -> Self {
+        ActivityRecord { last_active: at, active_since: at, previous_run_end: None, region: None }
+    }
+
+    /// Record a transaction at `at`. Older transactions are ignored.
+    pub fn record(&mut self, at: Timestamp, window: u64) {
+        if at <= self.last_active {
+            return;
+        }
+        if at - self.last_active >= window {
+            self.previous_run_end = Some(self.last_active);
+            self.active_since = at;
+        }
+        self.last_active = at;
+    }
+
+    /// Whether the record counts as active at `now` for a window of `window`.
+    pub fn is_active(&self, now: Timestamp, window: u64) -> bool {
+        self.last_active <= now && now - self.last_active < window
+    }
+
+    /// Whether the record counted as active at `at`, even if the identity
+    /// has transacted since. Exact while `at` lies less than `window` before
+    /// the present, which is how long votes snapshot their electorate for.
+    pub fn was_active(&self, at: Timestamp, window: u64) -> bool {
+        if at >= self.active_since {
+            return at < self.last_active.saturating_add(window);
+        }
+        self.previous_run_end.is_some_and(|end| end <= at && at < end.saturating_add(window))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct ActivityIndex<Id: Ord> {
+    records: BTreeMap<Id, ActivityRecord>,
+    window: u64,
+}
+
+impl<Id: Ord> Default for ActivityIndex<Id> {
+    fn default() -> Self {
+        Self::with_window(ACTIVITY_WINDOW_SECONDS)
+    }
+}
+
+impl<Id: Ord> ActivityIndex<Id> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An index with a custom window, e.g. in blocks instead of seconds.
+    pub fn with_window(window: u64) -> Self {
+        ActivityIndex { records: BTreeMap::new(), window }
+    }
+
+    /// Record a transaction by `identity` at `at`. Older records never
+    /// overwrite newer ones.
+    pub fn record(&mut self, identity: Id, at: Timestamp) {
+        self.records.entry(identity).or_insert(ActivityRecord::new(at)).record(at, self.window);
+    }
+
+    /// Declaring a region is itself on-chain activity.
+    pub fn declare_region(&mut self, identity: Id, region: GeoRegion, at: Timestamp) {
+        let record = self.records.entry(identity).or_insert(ActivityRecord::new(at));
+        record.record(at, self.window);
+        record.region = Some(region);
+    }
+
+    pub fn get(&self, identity: &Id) -> Option<&ActivityRecord> {
+        self.records.get(identity)
+    }
+
+    pub fn last_active(&self, identity: &Id) -> Option<Timestamp> {
+        self.get(identity).map(|r| r.last_active)
+    }
+
+    pub fn is_active(&self, identity: &Id, now: Timestamp) -> bool {
+        self.get(identity).is_some_and(|r| r.is_active(now, self.window))
+    }
+
+    /// Identities active at `now`.
+    pub fn active(&self, now: Timestamp) -> impl Iterator<Item = &Id> {
+        self.records.iter().filter(move |(_, r)| r.is_active(now, self.window)).map(|(id, _)| id)
+    }
+
+    pub fn active_count(&self, now: Timestamp) -> u64 {
+        self.active(now).count() as u64
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &ActivityRecord> {
+        self.records.values()
+    }
+
+    pub fn window(&self) -> u64 {
+        self.window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_was_active_survives_later_activity() {
+        let window = 10;
+        let mut record = ActivityRecord::new(0);
+        record.record(5, window);
+        assert!(record.was_active(14, window));
+        assert!(!record.was_active(15, window));
+
+        // A new run after a gap: the earlier run is still remembered.
+        record.record(30, window);
+        assert_eq!((record.active_since, record.previous_run_end), (30, Some(5)));
+        assert!(record.was_active(12, window));
+        assert!(!record.was_active(20, window));
+        assert!(record.was_active(35, window));
+        // Within a run, later activity extends it.
+        record.record(38, window);
+        assert_eq!(record.active_since, 30);
+        assert!(record.was_active(47, window));
+    }
+}
@@ -0,0 +1,108 @@
+//! Bootstrap Phase evaluation (GOVERNANCE_CONSTITUTION.md Art. III §4).
+//!
+//! Governance activates once enough active identities exist across enough
+//! regions. The §4.4 check against a press-freedom index needs data ratified
+//! off-chain and is not evaluated here.
+
+use crate::activity::{ActivityIndex, ActivityRecord};
+use crate::charter::{BOOTSTRAP_MIN_ACTIVE_IDENTITIES, BOOTSTRAP_MIN_REGIONS};
+use crate::council::GeoRegion;
+use crate::proposal::Timestamp;
+use crate::state::GovernanceState;
+use alloc::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+/// How far the network is from governance activation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct BootstrapProgress {
+    pub active_identities: u64,
+    /// Active identities that have not declared a region.
+    pub undeclared: u64,
+    /// Active identities per declared region.
+    pub regions: BTreeMap<GeoRegion, u64>,
+}
+
+impl BootstrapProgress {
+    /// Count the records active at `now` for a window of `window`.
+    pub fn evaluate<'a>(records: impl IntoIterator<Item = &'a ActivityRecord>, now: Timestamp, window: u64) -> Self {
+        let mut progress = BootstrapProgress::default();
+        for record in records.into_iter().filter(|r| r.is_active(now, window)) {
+            progress.active_identities += 1;
+            match record.region {
+                Some(region) => *progress.regions.entry(region).or_insert(0) += 1,
+                None => progress.undeclared += 1,
+            }
+        }
+        progress
+    }
+
+    pub fn from_index<Id: Ord>(index: &ActivityIndex<Id>, now: Timestamp) -> Self {
+        Self::evaluate(index.records(), now, index.window())
+    }
+
+    /// From running counts of active identities per declared region (`None`
+    /// for undeclared), e.g. the counters the runtime keeps instead of
+    /// scanning every record.
+    pub fn from_totals(totals: impl IntoIterator<Item = (Option<GeoRegion>, u64)>) -> Self {
+        let mut progress = BootstrapProgress::default();
+        for (region, count) in totals.into_iter().filter(|(_, count)| *count > 0) {
+            progress.active_identities += count;
+            match region {
+                Some(region) => *progress.regions.entry(region).or_insert(0) += count,
+                None => progress.undeclared += count,
+            }
+        }
+        progress
+    }
+
+    /// Active identities still needed.
+    pub fn identities_missing(&self) -> u64 {
+        BOOTSTRAP_MIN_ACTIVE_IDENTITIES.saturating_sub(self.active_identities)
+    }
+
+    /// Regions still needed.
+    pub fn regions_missing(&self) -> usize {
+        BOOTSTRAP_MIN_REGIONS.saturating_sub(self.regions.len())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.identities_missing() == 0 && self.regions_missing() == 0
+    }
+
+    /// Open the first general election if still bootstrapping and every
+    /// criterion holds. Returns whether it did.
+    pub fn activate(&self, state: &mut GovernanceState) -> bool {
+        *state == GovernanceState::Bootstrap && self.is_complete() && state.open_election().is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_and_activation() {
+        let regions = [GeoRegion::Europe, GeoRegion::NorthAmerica];
+        let mut index = ActivityIndex::new();
+        for id in 0..BOOTSTRAP_MIN_ACTIVE_IDENTITIES {
+            index.declare_region(id, regions[id as usize % 2], 0);
+        }
+        let mut state = GovernanceState::Bootstrap;
+
+        let progress = BootstrapProgress::from_index(&index, 10);
+        assert_eq!((progress.identities_missing(), progress.regions_missing()), (0, 1));
+        assert!(!progress.activate(&mut state));
+
+        index.declare_region(9_999, GeoRegion::AsiaPacific, 10);
+        let progress = BootstrapProgress::from_index(&index, 10);
+        let totals = progress.regions.iter().map(|(r, n)| (Some(*r), *n)).chain([(None, 0)]);
+        assert_eq!(BootstrapProgress::from_totals(totals), progress);
+        assert!(progress.activate(&mut state));
+        assert_eq!(state, GovernanceState::Electing);
+
+        // Everyone but the last identity has gone inactive.
+        let later = BootstrapProgress::from_index(&index, index.window());
+        assert_eq!(later.active_identities, 1);
+    }
+}
@@ -0,0 +1,85 @@
+//! Numbers fixed by GOVERNANCE_CONSTITUTION.md and GOVERNANCE_CHARTER.md.
+//!
+//! Every other module takes its constants and vote rules from here.
+
+use crate::vote::{Base, Fraction, VoteRule};
+use serde::{Deserialize, Serialize};
+
+/// Art. I §1: minimum seated members.
+pub const MIN_COUNCIL_MEMBERS: usize = 10;
+/// Art. I §2: term length.
+pub const TERM_YEARS: u32 = 4;
+/// Art. I §2: lifetime term limit.
+pub const MAX_TERMS: u32 = 2;
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+/// Art. I §2: term length in seconds, counting one leap day per four years.
+pub const TERM_SECONDS: u64 = (TERM_YEARS as u64 * 365 + TERM_YEARS as u64 / 4) * SECONDS_PER_DAY;
+
+/// Art. II §1: quorum of seated members.
+pub const COUNCIL_QUORUM: (u64, u64) = (66, 100);
+/// Art. II §1 and Art. V: amendment supermajority.
+pub const SUPERMAJORITY: (u64, u64) = (75, 100);
+
+/// Art. III §2: days between Votes of No Confidence.
+pub const NO_CONFIDENCE_COOLDOWN_DAYS: u32 = 90;
+/// Art. III §2: days a Vote of No Confidence stays open.
+pub const NO_CONFIDENCE_VOTING_DAYS: u32 = 14;
+/// Art. III §2 and §4: an identity is active if it transacted within this window.
+pub const ACTIVITY_WINDOW_DAYS: u32 = 90;
+
+/// Art. III §4: active identities required to leave the Bootstrap Phase.
+pub const BOOTSTRAP_MIN_ACTIVE_IDENTITIES: u64 = 1_000;
+/// Art. III §4: distinct geographic regions required.
+pub const BOOTSTRAP_MIN_REGIONS: usize = 3;
+
+/// Kinds of decision, each with its own vote rule.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum Decision {
+    /// Art. II §1: ordinary council business (ratifications, guidelines,
+    /// blacklists). Simple majority of the members present.
+    CouncilOrdinary,
+    /// Art. V, council half: 75% of the members present. Also needs
+    /// `CommunityRatification`.
+    ConstitutionalAmendment,
+    /// Art. IV §2, Art. V, charter: majority of community votes cast.
+    CommunityRatification,
+    /// Art. III §2: majority of all identities active in the window.
+    NoConfidence,
+}
+
+impl Decision {
+    pub fn rule(&self) -> VoteRule {
+        let majority = Fraction::new(1, 2);
+        let council_quorum = Fraction::new(COUNCIL_QUORUM.0, COUNCIL_QUORUM.1);
+        let no_quorum = Fraction::from_integer(0);
+
+        match self {
+            Decision::CouncilOrdinary => VoteRule {
+                quorum: council_quorum,
+                threshold: majority,
+                strict: true,
+                base: Base::Present,
+            },
+            Decision::ConstitutionalAmendment => VoteRule {
+                quorum: council_quorum,
+                threshold: Fraction::new(SUPERMAJORITY.0, SUPERMAJORITY.1),
+                strict: false,
+                base: Base::Present,
+            },
+            Decision::CommunityRatification => VoteRule {
+                quorum: no_quorum,
+                threshold: majority,
+                strict: true,
+                base: Base::Decisive,
+            },
+            Decision::NoConfidence => VoteRule {
+                quorum: no_quorum,
+                threshold: majority,
+                strict: true,
+                base: Base::Eligible,
+            },
+        }
+    }
+}
@@ -1,15 +1,271 @@
-pub const MIN_COUNCIL_MEMBERS: usize = 10;
+//! Council seat roster (GOVERNANCE_CONSTITUTION.md Art. I).
 
-pub struct Council {
-    pub members: usize,
+use crate::charter::{Decision, MAX_TERMS, TERM_SECONDS};
+use crate::proposal::Timestamp;
+use crate::vote::VoteCount;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+pub use crate::charter::MIN_COUNCIL_MEMBERS;
+
+/// Geographic region a member or identity declares.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub enum GeoRegion {
+    Africa,
+    AsiaPacific,
+    Europe,
+    LatinAmerica,
+    MiddleEast,
+    NorthAmerica,
+}
+
+/// One seated member.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct CouncilMember<Id> {
+    pub identity: Id,
+    pub region: GeoRegion,
+    pub term_start: Timestamp,
+    pub term_end: Timestamp,
+    /// Terms served including this one.
+    pub term_count: u32,
+    /// Index of the member's share in the council key set.
+    pub key_share: u32,
+}
+
+impl<Id> CouncilMember<Id> {
+    pub fn is_active(&self, now: Timestamp) -> bool {
+        self.term_start <= now && now < self.term_end
+    }
+
+    /// Whether the member may stand for another term.
+    pub fn can_be_reelected(&self) -> bool {
+        self.term_count < MAX_TERMS
+    }
+}
+
+/// Why an identity may not stand for the council.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ineligibility {
+    /// On the Global Revocation List.
+    Blacklisted,
+    /// Already served `MAX_TERMS` terms.
+    TermLimitReached,
+    /// Holds a seat whose term has not ended.
+    AlreadySeated,
+}
+
+impl fmt::Display for Ineligibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ineligibility::Blacklisted => write!(f, "Identity is on the Blacklist"),
+            Ineligibility::TermLimitReached => write!(f, "Identity has served {} terms", MAX_TERMS),
+            Ineligibility::AlreadySeated => write!(f, "Identity already holds a seat"),
+        }
+    }
+}
+
+/// The seated council and the lifetime term count of everyone who has served.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct Council<Id: Ord> {
+    seats: Vec<CouncilMember<Id>>,
+    terms_served: BTreeMap<Id, u32>,
+    term: u64,
+    dissolutions: u32,
 }
 
-impl Council {
-    pub fn new(members: usize) -> Self {
-        Self { members }
+impl<Id: Ord> Default for Council<Id> {
+    fn default() -> Self {
+        Self::with_term(TERM_SECONDS)
+    }
+}
+
+impl<Id: Ord> Council<Id> {
+    /// A council with a custom term length, e.g. in blocks instead of seconds.
+    pub fn with_term(term: u64) -> Self {
+        Council { seats: Vec::new(), terms_served: BTreeMap::new(), term, dissolutions: 0 }
+    }
+}
+
+impl<Id: Ord + Clone> Council<Id> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Length of a term, in the unit of the council's timestamps.
+    pub fn term(&self) -> u64 {
+        self.term
+    }
+
+    /// How often the council has been dissolved. Identifies the council a
+    /// Vote of No Confidence was initiated against.
+    pub fn dissolutions(&self) -> u32 {
+        self.dissolutions
+    }
+
+    pub fn seats(&self) -> &[CouncilMember<Id>] {
+        &self.seats
+    }
+
+    pub fn members(&self) -> usize {
+        self.seats.len()
+    }
+
+    pub fn member(&self, identity: &Id) -> Option<&CouncilMember<Id>> {
+        self.seats.iter().find(|m| &m.identity == identity)
     }
 
+    pub fn terms_served(&self, identity: &Id) -> u32 {
+        self.terms_served.get(identity).copied().unwrap_or(0)
+    }
+
+    /// Every reason `identity` may not stand for a term starting at
+    /// `term_start` (Art. I §1.2); empty if eligible. Sitting members may run
+    /// for the term that follows their own.
+    pub fn check_candidate(&self, identity: &Id, blacklisted: bool, term_start: Timestamp) -> Vec<Ineligibility> {
+        let mut reasons = Vec::new();
+        if blacklisted {
+            reasons.push(Ineligibility::Blacklisted);
+        }
+        if self.terms_served(identity) >= MAX_TERMS {
+            reasons.push(Ineligibility::TermLimitReached);
+        }
+        if self.member(identity).is_some_and(|m| m.term_end > term_start) {
+            reasons.push(Ineligibility::AlreadySeated);
+        }
+        reasons
+    }
+
+    /// Seat an elected candidate for a full term starting at `term_start`,
+    /// replacing their previous, ended seat if any.
+    pub fn seat(
+        &mut self,
+        identity: Id,
+        region: GeoRegion,
+        term_start: Timestamp,
+        key_share: u32,
+        blacklisted: bool,
+    ) -> Result<&CouncilMember<Id>, Vec<Ineligibility>> {
+        let reasons = self.check_candidate(&identity, blacklisted, term_start);
+        if !reasons.is_empty() {
+            return Err(reasons);
+        }
+
+        self.seats.retain(|m| m.identity != identity);
+        let term_count = self.terms_served(&identity) + 1;
+        self.terms_served.insert(identity.clone(), term_count);
+        self.seats.push(CouncilMember {
+            identity,
+            region,
+            term_start,
+            term_end: term_start.saturating_add(self.term),
+            term_count,
+            key_share,
+        });
+        Ok(&self.seats[self.seats.len() - 1])
+    }
+
+    /// Remove a member before their term ends. The term still counts towards
+    /// their limit.
+    pub fn remove(&mut self, identity: &Id) -> Option<CouncilMember<Id>> {
+        let index = self.seats.iter().position(|m| &m.identity == identity)?;
+        Some(self.seats.remove(index))
+    }
+
+    /// Dissolve the council (Art. III §1 and §2), returning the unseated
+    /// members. Their terms still count towards the limit.
+    pub fn dissolve(&mut self) -> Vec<CouncilMember<Id>> {
+        self.dissolutions = self.dissolutions.saturating_add(1);
+        core::mem::take(&mut self.seats)
+    }
+
+    /// Remove and return every seat whose term has ended by `now`.
+    pub fn expire(&mut self, now: Timestamp) -> Vec<CouncilMember<Id>> {
+        let (ended, seated) = core::mem::take(&mut self.seats).into_iter().partition(|m| m.term_end <= now);
+        self.seats = seated;
+        ended
+    }
+
+    /// When the next seat expires.
+    pub fn next_expiry(&self) -> Option<Timestamp> {
+        self.seats.iter().map(|m| m.term_end).min()
+    }
+
+    /// Seats whose term ends by `deadline`, e.g. to schedule an election.
+    pub fn expiring_by(&self, deadline: Timestamp) -> impl Iterator<Item = &CouncilMember<Id>> {
+        self.seats.iter().filter(move |m| m.term_end <= deadline)
+    }
+
+    /// Seats per region. Distribution is informational: the constitution
+    /// leaves it to voters (Art. I §1.3).
+    pub fn regions(&self) -> BTreeMap<GeoRegion, usize> {
+        let mut counts = BTreeMap::new();
+        for member in &self.seats {
+            *counts.entry(member.region).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Whether the council meets the constitutional minimum size (Art. I §1).
     pub fn has_quorum(&self) -> bool {
-        self.members >= MIN_COUNCIL_MEMBERS
+        self.members() >= MIN_COUNCIL_MEMBERS
+    }
+
+    /// Whether `present` members make a valid vote: 66% of seated members (Art. II §1).
+    pub fn quorum_met(&self, present: usize) -> bool {
+        let ballots = VoteCount { abstain: present as u64, ..Default::default() };
+        Decision::CouncilOrdinary.rule().tally(&ballots, self.members() as u64).quorum_margin >= 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_term_limit_and_reelection() {
+        let mut council = Council::new();
+        let first = council.seat(1u32, GeoRegion::Europe, 0, 1, false).unwrap();
+        assert_eq!((first.term_end, first.term_count), (TERM_SECONDS, 1));
+        assert!(first.can_be_reelected());
+
+        // Running for a term that overlaps the current one is refused.
+        assert_eq!(council.check_candidate(&1, false, 100), vec![Ineligibility::AlreadySeated]);
+
+        let second = council.seat(1, GeoRegion::Europe, TERM_SECONDS, 1, false).unwrap();
+        assert_eq!(second.term_count, 2);
+        assert!(!second.can_be_reelected());
+        assert_eq!(council.members(), 1);
+
+        assert_eq!(council.expire(2 * TERM_SECONDS).len(), 1);
+        assert_eq!(
+            council.check_candidate(&1, true, 2 * TERM_SECONDS),
+            vec![Ineligibility::Blacklisted, Ineligibility::TermLimitReached]
+        );
+    }
+
+    #[test]
+    fn test_quorum_and_expiry() {
+        let mut council = Council::new();
+        for id in 0..10u32 {
+            council.seat(id, GeoRegion::AsiaPacific, id as u64, id, false).unwrap();
+        }
+        assert!(council.has_quorum());
+        assert!(council.quorum_met(7));
+        assert!(!council.quorum_met(6));
+        assert_eq!(council.next_expiry(), Some(TERM_SECONDS));
+        assert_eq!(council.expiring_by(TERM_SECONDS + 1).count(), 2);
+        assert_eq!(council.regions()[&GeoRegion::AsiaPacific], 10);
+    }
+
+    #[test]
+    fn test_term_in_blocks() {
+        let mut council = Council::with_term(100);
+        assert_eq!(council.seat(1u32, GeoRegion::Europe, 50, 0, false).unwrap().term_end, 150);
+        assert_eq!(council.seat(2, GeoRegion::Europe, u64::MAX - 1, 1, false).unwrap().term_end, u64::MAX);
     }
 }
@@ -0,0 +1,267 @@
+//! Council elections (GOVERNANCE_CONSTITUTION.md Art. I §1, Art. III §1).
+//!
+//! Voters cast approval ballots: the set of candidates they support. The same
+//! ballots can be counted by plain approval or by sequential Phragmén, which
+//! spreads seats proportionally across groups of voters. Regional spread is
+//! left to voters; there are no quotas (Art. I §1.3), but among otherwise
+//! tied candidates the one whose region holds fewer of the seats won so far
+//! is elected first.
+//!
+//! Counting uses integers only, so every node gets the same result. Phragmén
+//! loads are fixed-point with 18 decimal places and rounded down; scores
+//! closer than that compare as equal, where an exact rational count might
+//! still tell them apart. Ties are broken by fewer seats won by the
+//! candidate's region, then more approvals, then the smaller identity in
+//! `Ord` order.
+
+use crate::council::{Council, GeoRegion, Ineligibility};
+use crate::proposal::Timestamp;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Fixed-point unit for Phragmén voter loads.
+const LOAD_UNIT: u128 = 1_000_000_000_000_000_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum ElectionMethod {
+    /// The candidates with the most approvals win.
+    Approval,
+    /// Sequential Phragmén: proportional representation of voter groups.
+    Phragmen,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ElectionError {
+    Ineligible(Vec<Ineligibility>),
+    AlreadyRegistered,
+    UnknownCandidate,
+    EmptyBallot,
+    AlreadyVoted,
+}
+
+impl fmt::Display for ElectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElectionError::Ineligible(reasons) => {
+                write!(f, "Candidate is not eligible:")?;
+                for reason in reasons {
+                    write!(f, " {}.", reason)?;
+                }
+                Ok(())
+            }
+            ElectionError::AlreadyRegistered => write!(f, "Candidate is already registered"),
+            ElectionError::UnknownCandidate => write!(f, "Ballot names an unregistered candidate"),
+            ElectionError::EmptyBallot => write!(f, "Ballot approves no candidate"),
+            ElectionError::AlreadyVoted => write!(f, "Voter has already cast a ballot"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ElectionError {}
+
+/// Outcome of a count.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ElectionResult<Id: Ord> {
+    /// Winners in the order they were elected.
+    pub elected: Vec<Id>,
+    /// Approvals per registered candidate.
+    pub approvals: BTreeMap<Id, u64>,
+}
+
+/// An election for `seats` seats with terms starting at `term_start`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct Election<Id: Ord> {
+    pub seats: u32,
+    pub method: ElectionMethod,
+    pub term_start: Timestamp,
+    candidates: BTreeMap<Id, GeoRegion>,
+    ballots: BTreeMap<Id, BTreeSet<Id>>,
+}
+
+impl<Id: Ord + Clone> Election<Id> {
+    pub fn new(seats: u32, method: ElectionMethod, term_start: Timestamp) -> Self {
+        Election { seats, method, term_start, candidates: BTreeMap::new(), ballots: BTreeMap::new() }
+    }
+
+    /// Register a candidate after checking term limits and the Blacklist
+    /// against `council`.
+    pub fn register(
+        &mut self,
+        council: &Council<Id>,
+        identity: Id,
+        region: GeoRegion,
+        blacklisted: bool,
+    ) -> Result<(), ElectionError> {
+        if self.candidates.contains_key(&identity) {
+            return Err(ElectionError::AlreadyRegistered);
+        }
+        let reasons = council.check_candidate(&identity, blacklisted, self.term_start);
+        if !reasons.is_empty() {
+            return Err(ElectionError::Ineligible(reasons));
+        }
+        self.candidates.insert(identity, region);
+        Ok(())
+    }
+
+    pub fn candidates(&self) -> impl Iterator<Item = (&Id, &GeoRegion)> {
+        self.candidates.iter()
+    }
+
+    pub fn region_of(&self, candidate: &Id) -> Option<GeoRegion> {
+        self.candidates.get(candidate).copied()
+    }
+
+    /// Cast one approval ballot. The caller checks that `voter` may vote.
+    pub fn cast(&mut self, voter: Id, approvals: impl IntoIterator<Item = Id>) -> Result<(), ElectionError> {
+        if self.ballots.contains_key(&voter) {
+            return Err(ElectionError::AlreadyVoted);
+        }
+        let approvals: BTreeSet<Id> = approvals.into_iter().collect();
+        if approvals.is_empty() {
+            return Err(ElectionError::EmptyBallot);
+        }
+        if approvals.iter().any(|c| !self.candidates.contains_key(c)) {
+            return Err(ElectionError::UnknownCandidate);
+        }
+        self.ballots.insert(voter, approvals);
+        Ok(())
+    }
+
+    pub fn ballots_cast(&self) -> usize {
+        self.ballots.len()
+    }
+
+    /// Count the ballots. Candidates without approvals are never elected, so
+    /// fewer than `seats` may win.
+    pub fn tally(&self) -> ElectionResult<Id> {
+        let mut approvals: BTreeMap<Id, u64> = self.candidates.keys().map(|c| (c.clone(), 0)).collect();
+        for ballot in self.ballots.values() {
+            for candidate in ballot {
+                *approvals.entry(candidate.clone()).or_insert(0) += 1;
+            }
+        }
+
+        let elected = match self.method {
+            ElectionMethod::Approval => self.approval(&approvals),
+            ElectionMethod::Phragmen => self.phragmen(&approvals),
+        };
+
+        ElectionResult { elected, approvals }
+    }
+
+    /// Seats in `elected` held by candidates from `candidate`'s region.
+    fn region_seats(&self, elected: &[Id], candidate: &Id) -> usize {
+        let region = self.region_of(candidate);
+        elected.iter().filter(|e| self.region_of(e) == region).count()
+    }
+
+    fn approval(&self, approvals: &BTreeMap<Id, u64>) -> Vec<Id> {
+        let mut elected: Vec<Id> = Vec::new();
+        while elected.len() < self.seats as usize {
+            let winner = approvals
+                .iter()
+                .filter(|(c, &n)| n > 0 && !elected.contains(c))
+                .min_by_key(|(c, &n)| (Reverse(n), self.region_seats(&elected, c), *c));
+            let Some((winner, _)) = winner else { break };
+            elected.push(winner.clone());
+        }
+        elected
+    }
+
+    fn phragmen(&self, approvals: &BTreeMap<Id, u64>) -> Vec<Id> {
+        let mut loads: BTreeMap<&Id, u128> = self.ballots.keys().map(|v| (v, 0)).collect();
+        let mut elected: Vec<Id> = Vec::new();
+
+        while elected.len() < self.seats as usize {
+            // The winner is the candidate whose supporters end up with the
+            // lowest load: (1 + sum of their loads) / supporters.
+            let winner = approvals
+                .iter()
+                .filter(|(c, &n)| n > 0 && !elected.contains(c))
+                .map(|(c, &n)| {
+                    let supporter_load: u128 =
+                        self.ballots.iter().filter(|(_, b)| b.contains(c)).map(|(v, _)| loads[v]).sum();
+                    let score = (LOAD_UNIT + supporter_load) / n as u128;
+                    (score, self.region_seats(&elected, c), Reverse(n), c)
+                })
+                .min();
+
+            let Some((score, _, _, winner)) = winner else { break };
+            for (voter, ballot) in &self.ballots {
+                if ballot.contains(winner) {
+                    loads.insert(voter, score);
+                }
+            }
+            elected.push(winner.clone());
+        }
+
+        elected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn election(method: ElectionMethod) -> Election<&'static str> {
+        let council = Council::new();
+        let mut election = Election::new(3, method, 0);
+        for candidate in ["a", "b", "c", "x", "y"] {
+            election.register(&council, candidate, GeoRegion::Europe, false).unwrap();
+        }
+        // Six voters back a, b and c; four back x and y.
+        for voter in ["v1", "v2", "v3", "v4", "v5", "v6"] {
+            election.cast(voter, ["a", "b", "c"]).unwrap();
+        }
+        for voter in ["w1", "w2", "w3", "w4"] {
+            election.cast(voter, ["x", "y"]).unwrap();
+        }
+        election
+    }
+
+    #[test]
+    fn test_approval_and_phragmen() {
+        assert_eq!(election(ElectionMethod::Approval).tally().elected, vec!["a", "b", "c"]);
+        // The minority group wins one of three seats.
+        assert_eq!(election(ElectionMethod::Phragmen).tally().elected, vec!["a", "x", "b"]);
+    }
+
+    #[test]
+    fn test_ties_favour_unrepresented_regions() {
+        for method in [ElectionMethod::Approval, ElectionMethod::Phragmen] {
+            let council = Council::new();
+            let mut election = Election::new(2, method, 0);
+            election.register(&council, "a", GeoRegion::Europe, false).unwrap();
+            election.register(&council, "b", GeoRegion::Europe, false).unwrap();
+            election.register(&council, "c", GeoRegion::Africa, false).unwrap();
+            for voter in ["v1", "v2", "v3"] {
+                election.cast(voter, ["a", "b", "c"]).unwrap();
+            }
+            // All tied: "a" wins on identity, then "c" over "b" on region.
+            assert_eq!(election.tally().elected, vec!["a", "c"]);
+        }
+    }
+
+    #[test]
+    fn test_registration_and_ballot_checks() {
+        let mut council = Council::new();
+        council.seat("a", GeoRegion::Africa, 0, 0, false).unwrap();
+        let mut election = Election::new(1, ElectionMethod::Approval, 10);
+
+        assert_eq!(
+            election.register(&council, "a", GeoRegion::Africa, false),
+            Err(ElectionError::Ineligible(vec![Ineligibility::AlreadySeated]))
+        );
+        election.register(&council, "b", GeoRegion::Africa, false).unwrap();
+        assert_eq!(election.register(&council, "b", GeoRegion::Africa, false), Err(ElectionError::AlreadyRegistered));
+        assert_eq!(election.cast("v", ["a"]), Err(ElectionError::UnknownCandidate));
+        election.cast("v", ["b"]).unwrap();
+        assert_eq!(election.cast("v", ["b"]), Err(ElectionError::AlreadyVoted));
+    }
+}
@@ -0,0 +1,418 @@
+//! Supplemental regional guidelines (GOVERNANCE_CHARTER.md, Constitution Art. I §3.2).
+//!
+//! The council proposes a guideline, or a new version of one, under its
+//! threshold signature. A community vote ratifies it and it takes effect at an
+//! activation time no earlier than the end of that vote. It stays in force
+//! until the council revokes it or a ratified amendment supersedes it.
+//!
+//! The community vote runs on chain. Clients learn its outcome from the
+//! `GuidelineRecord` the chain stores once it closes, read with a storage
+//! proof under a state root their light client has seen finalized, so a
+//! registry only moves past `Proposed` on the chain's word.
+
+use crate::proposal::Timestamp;
+use crate::ratification::GuidelineRecord;
+use crypto::threshold::{CombinedSignature, CouncilKeySet};
+use crypto::ContentHash;
+use platform_core::region::{CountryCode, RegionSet};
+use platform_core::state_proof::{self, StateProofError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Domain separator for council guideline proposals.
+const PROPOSAL_CONTEXT: &[u8] = b"share-guideline-v1";
+/// Domain separator for council guideline revocations.
+const REVOCATION_CONTEXT: &[u8] = b"share-guideline-revoke-v1";
+
+/// Where the governance pallet stores guideline vote records, by payload hash.
+const RECORDS_PALLET: &str = "Governance";
+const RECORDS_STORAGE: &str = "GuidelineRatifications";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuidelineStatus {
+    /// Proposed by the council, awaiting community ratification.
+    Proposed,
+    /// Ratified; in force from `activates_at`.
+    Ratified,
+    /// The community vote did not pass.
+    Rejected,
+    Revoked,
+    /// Replaced by a later ratified version; in force until `ends_at`.
+    Superseded,
+}
+
+/// What the council signs when proposing a guideline version.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GuidelineDraft {
+    pub id: u64,
+    /// 1 for a new guideline, previous version + 1 for an amendment.
+    pub version: u32,
+    /// Hash of the guideline text.
+    pub text_hash: ContentHash,
+    pub regions: RegionSet,
+}
+
+impl GuidelineDraft {
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let regions = self.regions.to_string();
+        [
+            PROPOSAL_CONTEXT,
+            &self.id.to_le_bytes(),
+            &self.version.to_le_bytes(),
+            self.text_hash.as_bytes(),
+            regions.as_bytes(),
+        ]
+        .concat()
+    }
+
+    /// The payload hash the community ratification vote must carry.
+    pub fn payload_hash(&self) -> [u8; 32] {
+        *ContentHash::hash(&self.signing_bytes()).as_bytes()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignedGuideline {
+    pub draft: GuidelineDraft,
+    pub signature: CombinedSignature,
+}
+
+/// The community vote on a draft, proven from chain state.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GuidelineVote {
+    pub record: GuidelineRecord,
+    /// Finalized state root the record was read under.
+    pub state_root: [u8; 32],
+    /// Trie nodes proving the record under `state_root`.
+    pub storage_proof: Vec<Vec<u8>>,
+}
+
+impl GuidelineVote {
+    /// Check the storage proof: the chain stored exactly this record for the
+    /// draft under `state_root`.
+    pub fn verify_state(&self) -> Result<(), GuidelineError> {
+        let key = state_proof::map_key(RECORDS_PALLET, RECORDS_STORAGE, &self.record.payload_hash);
+        let stored =
+            state_proof::read(&self.state_root, &self.storage_proof, &key).map_err(GuidelineError::StateProof)?;
+        if stored.as_deref() != Some(self.record.to_bytes().as_slice()) {
+            return Err(GuidelineError::RecordMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Guideline {
+    pub id: u64,
+    pub version: u32,
+    pub text_hash: ContentHash,
+    pub regions: RegionSet,
+    pub status: GuidelineStatus,
+    /// Set on ratification.
+    pub activates_at: Option<Timestamp>,
+    /// Set on revocation or supersession.
+    pub ends_at: Option<Timestamp>,
+}
+
+impl Guideline {
+    /// Whether the guideline was in force at `at`, regardless of region.
+    pub fn in_force(&self, at: Timestamp) -> bool {
+        self.activates_at.is_some_and(|start| start <= at) && self.ends_at.is_none_or(|end| at < end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuidelineError {
+    InvalidSignature,
+    UnknownGuideline { id: u64, version: u32 },
+    /// Versions must follow the latest version of the guideline.
+    UnexpectedVersion { expected: u32, found: u32 },
+    WrongStatus(GuidelineStatus),
+    /// The vote is not a community ratification of this draft.
+    WrongVote,
+    /// Activation must not precede the end of the ratification vote.
+    ActivationBeforeVote,
+    /// The vote's state root is not one the client saw finalized.
+    UnfinalizedState,
+    /// The storage proof cannot be read under the state root.
+    StateProof(StateProofError),
+    /// The state root holds no record, or a different one, for the draft.
+    RecordMismatch,
+}
+
+impl fmt::Display for GuidelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuidelineError::InvalidSignature => write!(f, "Guideline has an invalid council signature"),
+            GuidelineError::UnknownGuideline { id, version } => write!(f, "Unknown guideline {} v{}", id, version),
+            GuidelineError::UnexpectedVersion { expected, found } => {
+                write!(f, "Expected guideline version {}, found {}", expected, found)
+            }
+            GuidelineError::WrongStatus(status) => write!(f, "Guideline is {:?}", status),
+            GuidelineError::WrongVote => write!(f, "Vote is not a community ratification of this guideline"),
+            GuidelineError::ActivationBeforeVote => write!(f, "Activation precedes the end of the vote"),
+            GuidelineError::UnfinalizedState => write!(f, "Guideline vote refers to an unfinalized state root"),
+            GuidelineError::StateProof(err) => write!(f, "Invalid guideline vote proof: {}", err),
+            GuidelineError::RecordMismatch => write!(f, "Guideline vote does not match the record on chain"),
+        }
+    }
+}
+
+impl std::error::Error for GuidelineError {}
+
+/// Every version of every guideline.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GuidelineRegistry {
+    guidelines: BTreeMap<u64, Vec<Guideline>>,
+    finalized: BTreeSet<[u8; 32]>,
+}
+
+impl GuidelineRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: u64, version: u32) -> Option<&Guideline> {
+        self.guidelines.get(&id)?.get(version.checked_sub(1)? as usize)
+    }
+
+    /// Record a state root the client's light client has seen finalized.
+    pub fn note_finalized(&mut self, state_root: [u8; 32]) {
+        self.finalized.insert(state_root);
+    }
+
+    /// Record a council-signed proposal for a new guideline or amendment.
+    pub fn propose(&mut self, signed: &SignedGuideline, council: &CouncilKeySet) -> Result<(), GuidelineError> {
+        if !council.verify(&signed.draft.signing_bytes(), &signed.signature) {
+            return Err(GuidelineError::InvalidSignature);
+        }
+        self.record_proposal(signed.draft.clone())
+    }
+
+    fn record_proposal(&mut self, draft: GuidelineDraft) -> Result<(), GuidelineError> {
+        let versions = self.guidelines.entry(draft.id).or_default();
+        let expected = versions.len() as u32 + 1;
+        if draft.version != expected {
+            return Err(GuidelineError::UnexpectedVersion { expected, found: draft.version });
+        }
+        // A pending version must be decided first; a revoked guideline stays revoked.
+        if let Some(last) = versions.last() {
+            if matches!(last.status, GuidelineStatus::Proposed | GuidelineStatus::Revoked) {
+                return Err(GuidelineError::WrongStatus(last.status));
+            }
+        }
+        versions.push(Guideline {
+            id: draft.id,
+            version: draft.version,
+            text_hash: draft.text_hash,
+            regions: draft.regions,
+            status: GuidelineStatus::Proposed,
+            activates_at: None,
+            ends_at: None,
+        });
+        Ok(())
+    }
+
+    /// Apply the closed community vote on a proposed version. If it passed,
+    /// the version takes effect at `activates_at` and supersedes the last
+    /// ratified version from then on.
+    pub fn conclude_vote(
+        &mut self,
+        id: u64,
+        version: u32,
+        vote: &GuidelineVote,
+        activates_at: Timestamp,
+    ) -> Result<GuidelineStatus, GuidelineError> {
+        let guideline = self.get(id, version).ok_or(GuidelineError::UnknownGuideline { id, version })?;
+        if guideline.status != GuidelineStatus::Proposed {
+            return Err(GuidelineError::WrongStatus(guideline.status));
+        }
+        let draft = GuidelineDraft {
+            id,
+            version,
+            text_hash: guideline.text_hash,
+            regions: guideline.regions.clone(),
+        };
+        if vote.record.payload_hash != draft.payload_hash() {
+            return Err(GuidelineError::WrongVote);
+        }
+        if !self.finalized.contains(&vote.state_root) {
+            return Err(GuidelineError::UnfinalizedState);
+        }
+        vote.verify_state()?;
+
+        let versions = self.guidelines.get_mut(&id).expect("checked above");
+        let index = version as usize - 1;
+        let status = if vote.record.passed {
+            if activates_at < vote.record.closed_at {
+                return Err(GuidelineError::ActivationBeforeVote);
+            }
+            let previous = versions[..index].iter_mut().rev().find(|v| v.status == GuidelineStatus::Ratified);
+            if let Some(previous) = previous {
+                previous.status = GuidelineStatus::Superseded;
+                previous.ends_at = Some(activates_at);
+            }
+            versions[index].activates_at = Some(activates_at);
+            GuidelineStatus::Ratified
+        } else {
+            GuidelineStatus::Rejected
+        };
+        versions[index].status = status;
+        Ok(status)
+    }
+
+    /// The message the council signs to revoke a guideline at `at`.
+    pub fn revocation_bytes(id: u64, version: u32, at: Timestamp) -> Vec<u8> {
+        [REVOCATION_CONTEXT, &id.to_le_bytes(), &version.to_le_bytes(), &at.to_le_bytes()].concat()
+    }
+
+    /// Revoke a guideline from `at` on, under the council's signature: a
+    /// ratified one, or a superseded one before its successor takes effect.
+    pub fn revoke(
+        &mut self,
+        id: u64,
+        version: u32,
+        at: Timestamp,
+        signature: &CombinedSignature,
+        council: &CouncilKeySet,
+    ) -> Result<(), GuidelineError> {
+        if !council.verify(&Self::revocation_bytes(id, version, at), signature) {
+            return Err(GuidelineError::InvalidSignature);
+        }
+        self.record_revocation(id, version, at)
+    }
+
+    fn record_revocation(&mut self, id: u64, version: u32, at: Timestamp) -> Result<(), GuidelineError> {
+        let guideline = self
+            .guidelines
+            .get_mut(&id)
+            .and_then(|versions| versions.get_mut(version.checked_sub(1)? as usize))
+            .ok_or(GuidelineError::UnknownGuideline { id, version })?;
+        match guideline.status {
+            GuidelineStatus::Ratified => {}
+            GuidelineStatus::Superseded if guideline.ends_at.is_some_and(|end| at < end) => {}
+            status => return Err(GuidelineError::WrongStatus(status)),
+        }
+        guideline.status = GuidelineStatus::Revoked;
+        guideline.ends_at = Some(at);
+        Ok(())
+    }
+
+    /// Guidelines in force for `region` at `at`.
+    pub fn active(&self, region: CountryCode, at: Timestamp) -> impl Iterator<Item = &Guideline> {
+        self.guidelines
+            .values()
+            .flatten()
+            .filter(move |g| g.in_force(at) && g.regions.contains(region))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(version: u32, regions: &str) -> GuidelineDraft {
+        GuidelineDraft {
+            id: 7,
+            version,
+            text_hash: ContentHash::hash(&version.to_le_bytes()),
+            regions: RegionSet::parse(regions).unwrap(),
+        }
+    }
+
+    /// The vote on `draft`, closed at 100, proven from a state holding
+    /// nothing but its record.
+    fn vote(draft: &GuidelineDraft, passed: bool) -> GuidelineVote {
+        let record = GuidelineRecord { payload_hash: draft.payload_hash(), proposal: 1, closed_at: 100, passed };
+        let key = state_proof::map_key(RECORDS_PALLET, RECORDS_STORAGE, &record.payload_hash);
+        let (state_root, storage_proof) = state_proof::single_entry(&key, &record.to_bytes());
+        GuidelineVote { record, state_root, storage_proof }
+    }
+
+    #[test]
+    fn test_ratify_amend_revoke() {
+        let de: CountryCode = "DE".parse().unwrap();
+        let fr: CountryCode = "FR".parse().unwrap();
+        let mut registry = GuidelineRegistry::new();
+
+        let v1 = draft(1, "DE");
+        registry.record_proposal(v1.clone()).unwrap();
+        assert_eq!(
+            registry.record_proposal(draft(2, "DE")),
+            Err(GuidelineError::WrongStatus(GuidelineStatus::Proposed))
+        );
+        let vote1 = vote(&v1, true);
+        assert_eq!(registry.conclude_vote(7, 1, &vote1, 200), Err(GuidelineError::UnfinalizedState));
+        registry.note_finalized(vote1.state_root);
+        assert_eq!(registry.conclude_vote(7, 1, &vote1, 50), Err(GuidelineError::ActivationBeforeVote));
+        assert_eq!(registry.conclude_vote(7, 1, &vote1, 200), Ok(GuidelineStatus::Ratified));
+        assert_eq!(registry.active(de, 150).count(), 0);
+        assert_eq!(registry.active(de, 200).count(), 1);
+
+        let v2 = draft(2, "EU");
+        registry.record_proposal(v2.clone()).unwrap();
+        assert_eq!(registry.conclude_vote(7, 2, &vote1, 300), Err(GuidelineError::WrongVote));
+        let vote2 = vote(&v2, true);
+        registry.note_finalized(vote2.state_root);
+        registry.conclude_vote(7, 2, &vote2, 300).unwrap();
+        assert_eq!(registry.active(de, 250).map(|g| g.version).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(registry.active(fr, 300).map(|g| g.version).collect::<Vec<_>>(), vec![2]);
+
+        // Version 1 stays in force until version 2 takes effect, and can be
+        // revoked until then.
+        assert_eq!(registry.get(7, 1).unwrap().status, GuidelineStatus::Superseded);
+        assert_eq!(
+            registry.record_revocation(7, 1, 300),
+            Err(GuidelineError::WrongStatus(GuidelineStatus::Superseded))
+        );
+        registry.record_revocation(7, 1, 250).unwrap();
+        assert_eq!(registry.active(de, 240).count(), 1);
+        assert_eq!(registry.active(de, 250).count(), 0);
+        assert_eq!(registry.get(7, 1).unwrap().status, GuidelineStatus::Revoked);
+
+        registry.record_revocation(7, 2, 400).unwrap();
+        assert_eq!(registry.active(fr, 400).count(), 0);
+    }
+
+    #[test]
+    fn test_rejected_vote() {
+        let mut registry = GuidelineRegistry::new();
+        let v1 = draft(1, "FR");
+        registry.record_proposal(v1.clone()).unwrap();
+        let rejected = vote(&v1, false);
+        registry.note_finalized(rejected.state_root);
+        assert_eq!(registry.conclude_vote(7, 1, &rejected, 200), Ok(GuidelineStatus::Rejected));
+        assert_eq!(
+            registry.record_revocation(7, 1, 300),
+            Err(GuidelineError::WrongStatus(GuidelineStatus::Rejected))
+        );
+        // A rejected draft can be reworked as the next version.
+        registry.record_proposal(draft(2, "FR")).unwrap();
+    }
+
+    #[test]
+    fn test_vote_must_match_chain_record() {
+        let mut registry = GuidelineRegistry::new();
+        let v1 = draft(1, "FR");
+        registry.record_proposal(v1.clone()).unwrap();
+        let recorded = vote(&v1, false);
+        registry.note_finalized(recorded.state_root);
+
+        let flipped = GuidelineVote {
+            record: GuidelineRecord { passed: true, ..recorded.record.clone() },
+            ..recorded.clone()
+        };
+        assert_eq!(registry.conclude_vote(7, 1, &flipped, 200), Err(GuidelineError::RecordMismatch));
+
+        // A record fabricated with its own trie, claiming the finalized root.
+        let forged = GuidelineVote { state_root: recorded.state_root, ..vote(&v1, true) };
+        assert_eq!(
+            registry.conclude_vote(7, 1, &forged, 200),
+            Err(GuidelineError::StateProof(StateProofError::MissingNode(recorded.state_root)))
+        );
+
+        assert_eq!(registry.get(7, 1).unwrap().status, GuidelineStatus::Proposed);
+        assert_eq!(registry.conclude_vote(7, 1, &recorded, 200), Ok(GuidelineStatus::Rejected));
+    }
+}
@@ -1,8 +1,22 @@
-//! Governance module: council logic, quorum, dissolution
+//! Governance module: council logic, quorum, proposals, elections, guidelines, dissolution
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+pub mod activity;
+pub mod bootstrap;
+pub mod charter;
 pub mod council;
+pub mod election;
+#[cfg(feature = "std")]
+pub mod guideline;
+pub mod no_confidence;
+pub mod proposal;
+pub mod ratification;
+pub mod state;
 pub mod vote;
 
+#[cfg(feature = "std")]
 pub fn init() {
     println!("Governance module initialized");
 }
@@ -0,0 +1,335 @@
+//! Community Vote of No Confidence (GOVERNANCE_CONSTITUTION.md Art. III §2).
+//!
+//! The electorate is snapshotted when the vote is initiated: everyone active
+//! at that moment may vote, and the motion passes if a majority of them vote
+//! yes by the end of the window. Passing dissolves the council and puts
+//! governance into Caretaker Mode (Art. III §3).
+//!
+//! `initiate` and `vote` keep the electorate in memory. The runtime keeps the
+//! electorate and ballots in its own storage instead and drives the vote with
+//! `open` and `cast`, so only the tally is encoded.
+
+use crate::activity::ActivityIndex;
+use crate::charter::{NO_CONFIDENCE_COOLDOWN_DAYS, NO_CONFIDENCE_VOTING_DAYS, SECONDS_PER_DAY};
+use crate::council::{Council, CouncilMember};
+use crate::proposal::{Ballot, Proposal, ProposalError, ProposalEvent, ProposalKind, ProposalStatus, Schedule, Timestamp};
+use crate::state::{GovernanceState, StateError};
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+pub const COOLDOWN_SECONDS: u64 = NO_CONFIDENCE_COOLDOWN_DAYS as u64 * SECONDS_PER_DAY;
+pub const VOTING_SECONDS: u64 = NO_CONFIDENCE_VOTING_DAYS as u64 * SECONDS_PER_DAY;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoConfidenceError {
+    /// The previous vote ended less than the cooldown ago.
+    Cooldown { until: Timestamp },
+    AlreadyOpen,
+    NotOpen,
+    /// Only identities active when the vote was initiated may start or join it.
+    NotEligible,
+    /// There is no seated council to vote on.
+    State(StateError),
+    Proposal(ProposalError),
+}
+
+impl fmt::Display for NoConfidenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoConfidenceError::Cooldown { until } => write!(f, "No new Vote of No Confidence before {}", until),
+            NoConfidenceError::AlreadyOpen => write!(f, "A Vote of No Confidence is already open"),
+            NoConfidenceError::NotOpen => write!(f, "No Vote of No Confidence is open"),
+            NoConfidenceError::NotEligible => write!(f, "Identity was not active when the vote was initiated"),
+            NoConfidenceError::State(e) => write!(f, "{}", e),
+            NoConfidenceError::Proposal(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoConfidenceError {}
+
+impl From<ProposalError> for NoConfidenceError {
+    fn from(e: ProposalError) -> Self {
+        NoConfidenceError::Proposal(e)
+    }
+}
+
+/// How a concluded vote ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict<Id> {
+    /// The council was dissolved; these members lost their seats.
+    Dissolved(Vec<CouncilMember<Id>>),
+    Retained,
+    /// The council under vote was dissolved for another reason before the
+    /// vote closed; the vote was dropped and the sitting council keeps its
+    /// seats.
+    Void,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct NoConfidence<Id: Ord> {
+    /// When the last vote closed.
+    pub last_concluded: Option<Timestamp>,
+    current: Option<Proposal<Id>>,
+    /// `Council::dissolutions` of the council under vote.
+    council: u32,
+    #[cfg_attr(feature = "scale", codec(skip))]
+    electorate: BTreeSet<Id>,
+    votes_held: u64,
+    cooldown: u64,
+    voting: u64,
+}
+
+impl<Id: Ord> Default for NoConfidence<Id> {
+    fn default() -> Self {
+        Self::with_periods(COOLDOWN_SECONDS, VOTING_SECONDS)
+    }
+}
+
+impl<Id: Ord> NoConfidence<Id> {
+    /// Custom cooldown and voting periods, e.g. in blocks instead of seconds.
+    pub fn with_periods(cooldown: u64, voting: u64) -> Self {
+        NoConfidence {
+            last_concluded: None,
+            current: None,
+            council: 0,
+            electorate: BTreeSet::new(),
+            votes_held: 0,
+            cooldown,
+            voting,
+        }
+    }
+}
+
+impl<Id: Ord + Clone> NoConfidence<Id> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The open vote, if any.
+    pub fn current(&self) -> Option<&Proposal<Id>> {
+        self.current.as_ref()
+    }
+
+    /// Earliest time a new vote may be initiated.
+    pub fn next_allowed(&self) -> Timestamp {
+        self.last_concluded.map_or(0, |at| at.saturating_add(self.cooldown))
+    }
+
+    /// Open a vote on `council` at `now` with the identities currently active
+    /// in `activity` as electorate. Governance must be `Active`.
+    pub fn initiate(
+        &mut self,
+        initiator: Id,
+        activity: &ActivityIndex<Id>,
+        council: &Council<Id>,
+        state: &GovernanceState,
+        now: Timestamp,
+    ) -> Result<Vec<ProposalEvent>, NoConfidenceError> {
+        self.check_can_open(state, now)?;
+        if !activity.is_active(&initiator, now) {
+            return Err(NoConfidenceError::NotEligible);
+        }
+
+        let electorate: BTreeSet<Id> = activity.active(now).cloned().collect();
+        let events = self.open(initiator, electorate.len() as u64, council.dissolutions(), state, now)?;
+        self.electorate = electorate;
+        Ok(events)
+    }
+
+    /// Open a vote at `now` with an electorate of `eligible` identities the
+    /// caller has snapshotted itself. The caller checks that `initiator` is
+    /// among them and that later voters are, too. `council` is the
+    /// `Council::dissolutions` of the council under vote; callers without a
+    /// `Council` pass their own count and `cancel` on every dissolution.
+    pub fn open(
+        &mut self,
+        initiator: Id,
+        eligible: u64,
+        council: u32,
+        state: &GovernanceState,
+        now: Timestamp,
+    ) -> Result<Vec<ProposalEvent>, NoConfidenceError> {
+        self.check_can_open(state, now)?;
+        let schedule = Schedule { created: now, opens: now, closes: now.saturating_add(self.voting) };
+        let kind = ProposalKind::NoConfidence;
+        let mut proposal =
+            Proposal::new(self.votes_held, kind, [0u8; 32], initiator, schedule, kind.default_rule(), eligible)?;
+        let events = proposal.tick(now);
+
+        self.votes_held += 1;
+        self.council = council;
+        self.electorate.clear();
+        self.current = Some(proposal);
+        Ok(events)
+    }
+
+    fn check_can_open(&self, state: &GovernanceState, now: Timestamp) -> Result<(), NoConfidenceError> {
+        state.check_council_action().map_err(NoConfidenceError::State)?;
+        if self.current.is_some() {
+            return Err(NoConfidenceError::AlreadyOpen);
+        }
+        if now < self.next_allowed() {
+            return Err(NoConfidenceError::Cooldown { until: self.next_allowed() });
+        }
+        Ok(())
+    }
+
+    pub fn vote(&mut self, voter: Id, ballot: Ballot, now: Timestamp) -> Result<Vec<ProposalEvent>, NoConfidenceError> {
+        let proposal = self.current.as_mut().ok_or(NoConfidenceError::NotOpen)?;
+        if !self.electorate.contains(&voter) {
+            return Err(NoConfidenceError::NotEligible);
+        }
+        Ok(proposal.vote(voter, ballot, now)?)
+    }
+
+    /// Count a ballot in a vote opened with `open`. The caller has checked
+    /// that the voter belongs to its electorate and has not voted yet.
+    pub fn cast(&mut self, ballot: Ballot, now: Timestamp) -> Result<Vec<ProposalEvent>, NoConfidenceError> {
+        let proposal = self.current.as_mut().ok_or(NoConfidenceError::NotOpen)?;
+        Ok(proposal.cast(ballot, now)?)
+    }
+
+    /// Close the open vote once its window has passed. Returns whether the
+    /// motion carried, or `None` while the vote is still open or none exists.
+    /// Callers that keep their own roster apply the outcome themselves.
+    pub fn conclude(&mut self, now: Timestamp) -> Option<bool> {
+        let proposal = self.current.as_mut()?;
+        proposal.tick(now);
+        if !proposal.status.is_closed() {
+            return None;
+        }
+
+        let proposal = self.current.take()?;
+        self.electorate.clear();
+        self.last_concluded = Some(proposal.schedule.closes);
+        Some(proposal.status == ProposalStatus::Passed)
+    }
+
+    /// Drop the open vote without a verdict, e.g. because the council under
+    /// vote was dissolved for another reason. No cooldown starts.
+    pub fn cancel(&mut self) -> Option<Proposal<Id>> {
+        self.electorate.clear();
+        self.current.take()
+    }
+
+    /// `conclude`, then dissolve `council` and enter Caretaker Mode if the
+    /// motion carried. A vote on a council that has since been dissolved is
+    /// dropped instead: it never unseats its successor.
+    pub fn tick(
+        &mut self,
+        now: Timestamp,
+        council: &mut Council<Id>,
+        state: &mut GovernanceState,
+    ) -> Option<Verdict<Id>> {
+        if self.current.is_some() && self.council != council.dissolutions() {
+            self.cancel();
+            return Some(Verdict::Void);
+        }
+        if !self.conclude(now)? {
+            return Some(Verdict::Retained);
+        }
+        if state.dissolve().is_err() {
+            return Some(Verdict::Void);
+        }
+        Some(Verdict::Dissolved(council.dissolve()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::council::GeoRegion;
+
+    #[test]
+    fn test_majority_of_active_dissolves_and_cooldown_applies() {
+        let mut activity = ActivityIndex::new();
+        for id in 0..5u32 {
+            activity.record(id, 1_000);
+        }
+        let mut council = Council::new();
+        council.seat(100, GeoRegion::Europe, 0, 0, false).unwrap();
+        let mut state = GovernanceState::Active;
+        let mut nc = NoConfidence::new();
+
+        nc.initiate(0, &activity, &council, &state, 2_000).unwrap();
+        assert_eq!(nc.initiate(1, &activity, &council, &state, 2_001), Err(NoConfidenceError::AlreadyOpen));
+        assert_eq!(nc.vote(9, Ballot::Yes, 2_001), Err(NoConfidenceError::NotEligible));
+        for id in 0..3 {
+            nc.vote(id, Ballot::Yes, 2_001).unwrap();
+        }
+
+        assert_eq!(nc.tick(2_002, &mut council, &mut state), None);
+        let closes = 2_000 + VOTING_SECONDS;
+        let verdict = nc.tick(closes, &mut council, &mut state);
+        assert!(matches!(verdict, Some(Verdict::Dissolved(ref unseated)) if unseated.len() == 1));
+        assert_eq!(council.members(), 0);
+        assert_eq!(state, GovernanceState::Caretaker);
+
+        activity.record(0, closes);
+        assert_eq!(
+            nc.initiate(0, &activity, &council, &state, closes + 1),
+            Err(NoConfidenceError::State(StateError::Caretaker))
+        );
+        // Even once a new council is seated, the cooldown runs from the last vote.
+        assert_eq!(
+            nc.initiate(0, &activity, &council, &GovernanceState::Active, closes + 1),
+            Err(NoConfidenceError::Cooldown { until: closes + COOLDOWN_SECONDS })
+        );
+    }
+
+    #[test]
+    fn test_half_of_active_is_not_a_majority() {
+        let mut activity = ActivityIndex::new();
+        for id in 0..4u32 {
+            activity.record(id, 0);
+        }
+        let mut council = Council::<u32>::new();
+        let mut state = GovernanceState::Active;
+        let mut nc = NoConfidence::new();
+        nc.initiate(0, &activity, &council, &state, 10).unwrap();
+        nc.vote(0, Ballot::Yes, 11).unwrap();
+        nc.vote(1, Ballot::Yes, 11).unwrap();
+        // Two of four active identities is not a majority.
+        assert_eq!(nc.tick(10 + VOTING_SECONDS, &mut council, &mut state), Some(Verdict::Retained));
+        assert_eq!(state, GovernanceState::Active);
+    }
+
+    #[test]
+    fn test_vote_on_dissolved_council_does_not_unseat_successor() {
+        let mut activity = ActivityIndex::new();
+        for id in 0..5u32 {
+            activity.record(id, 0);
+        }
+        let mut council = Council::new();
+        for id in 100..110 {
+            council.seat(id, GeoRegion::Europe, 0, 0, false).unwrap();
+        }
+        let mut state = GovernanceState::Active;
+        let mut nc = NoConfidence::new();
+        nc.initiate(0, &activity, &council, &state, 10).unwrap();
+        for id in 0..3 {
+            nc.vote(id, Ballot::Yes, 11).unwrap();
+        }
+
+        // The council falls below quorum for another reason and a new one is
+        // elected before the vote closes.
+        state.dissolve().unwrap();
+        council.dissolve();
+        state.open_election().unwrap();
+        for id in 200..210 {
+            council.seat(id, GeoRegion::Africa, 20, 0, false).unwrap();
+        }
+        state.seat_council(&council).unwrap();
+
+        assert_eq!(nc.tick(10 + VOTING_SECONDS, &mut council, &mut state), Some(Verdict::Void));
+        assert_eq!((council.members(), state), (10, GovernanceState::Active));
+        assert_eq!(nc.current(), None);
+        // A dropped vote starts no cooldown.
+        nc.initiate(0, &activity, &council, &state, 11 + VOTING_SECONDS).unwrap();
+    }
+}
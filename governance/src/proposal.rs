@@ -0,0 +1,306 @@
+//! Proposal lifecycle.
+//!
+//! A pure state machine: it never reads a clock or storage, only the ballots
+//! and times it is given. Clients run it to display proposals and the runtime
+//! pallet runs the same code to enforce them, so both always agree.
+//!
+//! ```text
+//! Deliberation --opens--> Voting --closes--> Passed --execute--> Executed
+//!                                        \-> Rejected
+//!                                        \-> Expired (no quorum)
+//! ```
+
+use crate::charter::Decision;
+use crate::vote::{Outcome, Tally, VoteCount, VoteRule};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Seconds since the Unix epoch, or block numbers in the runtime. All times
+/// given to one proposal must use the same unit.
+pub type Timestamp = u64;
+
+/// What a proposal asks for. The payload it refers to is identified by hash.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub enum ProposalKind {
+    /// Ratify a software update, fork or protocol change (Art. I §3.1).
+    Ratification,
+    /// Adopt or revoke a supplemental guideline (Art. I §3.2).
+    Guideline,
+    /// Add or remove Blacklist entries (Art. I §3.3).
+    Blacklist,
+    /// Amend the Constitution (Art. V).
+    ConstitutionalAmendment,
+    /// Community vote to dissolve the council (Art. III §2).
+    NoConfidence,
+    /// Community vote ratifying a fork the council refused (Art. IV §2).
+    CommunityRatification,
+}
+
+impl ProposalKind {
+    /// The vote rule the charter sets for this kind.
+    pub fn default_rule(&self) -> VoteRule {
+        match self {
+            ProposalKind::ConstitutionalAmendment => Decision::ConstitutionalAmendment.rule(),
+            ProposalKind::NoConfidence => Decision::NoConfidence.rule(),
+            ProposalKind::CommunityRatification => Decision::CommunityRatification.rule(),
+            _ => Decision::CouncilOrdinary.rule(),
+        }
+    }
+
+    /// Whether the community votes on this kind rather than the council.
+    pub fn is_community(&self) -> bool {
+        matches!(self, ProposalKind::NoConfidence | ProposalKind::CommunityRatification)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub enum Ballot {
+    Yes,
+    No,
+    Abstain,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub enum ProposalStatus {
+    /// Created; ballots are not accepted yet.
+    Deliberation,
+    Voting,
+    Passed,
+    Rejected,
+    /// Closed without quorum.
+    Expired,
+    Executed,
+}
+
+impl ProposalStatus {
+    /// True once no further ballots or transitions except execution can happen.
+    pub fn is_closed(&self) -> bool {
+        !matches!(self, ProposalStatus::Deliberation | ProposalStatus::Voting)
+    }
+}
+
+/// Emitted on each state change.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum ProposalEvent {
+    Opened,
+    QuorumReached,
+    Passed(Tally),
+    Rejected(Tally),
+    Expired(Tally),
+    Executed,
+}
+
+/// When a proposal is created, opens for voting and closes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct Schedule {
+    pub created: Timestamp,
+    /// End of deliberation, start of voting.
+    pub opens: Timestamp,
+    /// End of voting.
+    pub closes: Timestamp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProposalError {
+    /// Times are not `created <= opens < closes`.
+    InvalidSchedule,
+    /// Ballots are only accepted while voting is open.
+    NotVoting(ProposalStatus),
+    AlreadyVoted,
+    /// Only passed proposals can be executed.
+    NotPassed(ProposalStatus),
+}
+
+impl fmt::Display for ProposalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProposalError::InvalidSchedule => write!(f, "Proposal schedule must satisfy created <= opens < closes"),
+            ProposalError::NotVoting(status) => write!(f, "Proposal is not open for voting ({:?})", status),
+            ProposalError::AlreadyVoted => write!(f, "Voter has already cast a ballot"),
+            ProposalError::NotPassed(status) => write!(f, "Proposal cannot be executed ({:?})", status),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProposalError {}
+
+/// A governance proposal, generic over the voter identity type.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct Proposal<V: Ord> {
+    pub id: u64,
+    pub kind: ProposalKind,
+    /// Hash of the proposed payload (fork descriptor, guideline text, ...).
+    pub payload_hash: [u8; 32],
+    pub proposer: V,
+    pub schedule: Schedule,
+    pub rule: VoteRule,
+    /// Size of the electorate, fixed when the proposal is created.
+    pub eligible: u64,
+    pub status: ProposalStatus,
+    pub votes: VoteCount,
+    /// Who voted how. The runtime keeps ballots in its own storage and
+    /// counts them with `cast`, so they are not part of the SCALE encoding.
+    #[cfg_attr(feature = "scale", codec(skip))]
+    ballots: BTreeMap<V, Ballot>,
+    quorum_reached: bool,
+}
+
+impl<V: Ord> Proposal<V> {
+    /// Create a proposal in deliberation. Call `tick` to open it if `opens`
+    /// has already passed.
+    pub fn new(
+        id: u64,
+        kind: ProposalKind,
+        payload_hash: [u8; 32],
+        proposer: V,
+        schedule: Schedule,
+        rule: VoteRule,
+        eligible: u64,
+    ) -> Result<Self, ProposalError> {
+        if !(schedule.created <= schedule.opens && schedule.opens < schedule.closes) {
+            return Err(ProposalError::InvalidSchedule);
+        }
+        Ok(Proposal {
+            id,
+            kind,
+            payload_hash,
+            proposer,
+            schedule,
+            rule,
+            eligible,
+            status: ProposalStatus::Deliberation,
+            votes: VoteCount::default(),
+            ballots: BTreeMap::new(),
+            quorum_reached: false,
+        })
+    }
+
+    /// Advance to `now`: open voting, or close it and record the outcome.
+    pub fn tick(&mut self, now: Timestamp) -> Vec<ProposalEvent> {
+        let mut events = Vec::new();
+
+        if self.status == ProposalStatus::Deliberation && now >= self.schedule.opens {
+            self.status = ProposalStatus::Voting;
+            events.push(ProposalEvent::Opened);
+        }
+
+        if self.status == ProposalStatus::Voting && now >= self.schedule.closes {
+            let tally = self.tally();
+            let (status, event) = match tally.outcome {
+                Outcome::Passed => (ProposalStatus::Passed, ProposalEvent::Passed(tally)),
+                Outcome::Failed => (ProposalStatus::Rejected, ProposalEvent::Rejected(tally)),
+                Outcome::NoQuorum => (ProposalStatus::Expired, ProposalEvent::Expired(tally)),
+            };
+            self.status = status;
+            events.push(event);
+        }
+
+        events
+    }
+
+    /// Record a ballot at time `now`. The caller checks that `voter` is eligible.
+    pub fn vote(&mut self, voter: V, ballot: Ballot, now: Timestamp) -> Result<Vec<ProposalEvent>, ProposalError> {
+        let mut events = self.tick(now);
+        if self.status != ProposalStatus::Voting {
+            return Err(ProposalError::NotVoting(self.status));
+        }
+        if self.ballots.contains_key(&voter) {
+            return Err(ProposalError::AlreadyVoted);
+        }
+
+        self.ballots.insert(voter, ballot);
+        events.extend(self.cast(ballot, now)?);
+        Ok(events)
+    }
+
+    /// Count a ballot at time `now` without recording who cast it, for
+    /// callers that keep ballots themselves and reject duplicates first.
+    pub fn cast(&mut self, ballot: Ballot, now: Timestamp) -> Result<Vec<ProposalEvent>, ProposalError> {
+        let mut events = self.tick(now);
+        if self.status != ProposalStatus::Voting {
+            return Err(ProposalError::NotVoting(self.status));
+        }
+
+        match ballot {
+            Ballot::Yes => self.votes.yes += 1,
+            Ballot::No => self.votes.no += 1,
+            Ballot::Abstain => self.votes.abstain += 1,
+        }
+
+        if !self.quorum_reached && self.tally().quorum_margin >= 0 {
+            self.quorum_reached = true;
+            events.push(ProposalEvent::QuorumReached);
+        }
+        Ok(events)
+    }
+
+    /// Mark a passed proposal as carried out.
+    pub fn execute(&mut self, now: Timestamp) -> Result<Vec<ProposalEvent>, ProposalError> {
+        let mut events = self.tick(now);
+        if self.status != ProposalStatus::Passed {
+            return Err(ProposalError::NotPassed(self.status));
+        }
+        self.status = ProposalStatus::Executed;
+        events.push(ProposalEvent::Executed);
+        Ok(events)
+    }
+
+    /// The current count under this proposal's rule.
+    pub fn tally(&self) -> Tally {
+        self.rule.tally(&self.votes, self.eligible)
+    }
+
+    pub fn ballot_of(&self, voter: &V) -> Option<Ballot> {
+        self.ballots.get(voter).copied()
+    }
+
+    /// Every ballot cast, ordered by voter.
+    pub fn ballots(&self) -> impl Iterator<Item = (&V, Ballot)> {
+        self.ballots.iter().map(|(voter, &ballot)| (voter, ballot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(kind: ProposalKind) -> Proposal<u32> {
+        let schedule = Schedule { created: 0, opens: 10, closes: 20 };
+        Proposal::new(1, kind, [7u8; 32], 0, schedule, kind.default_rule(), 10).unwrap()
+    }
+
+    #[test]
+    fn test_lifecycle_to_execution() {
+        let mut p = proposal(ProposalKind::Guideline);
+        assert_eq!(p.vote(1, Ballot::Yes, 5), Err(ProposalError::NotVoting(ProposalStatus::Deliberation)));
+        assert_eq!(p.tick(10), vec![ProposalEvent::Opened]);
+
+        for voter in 1..=6 {
+            p.vote(voter, Ballot::Yes, 11).unwrap();
+        }
+        assert_eq!(p.vote(7, Ballot::No, 12).unwrap(), vec![ProposalEvent::QuorumReached]);
+        assert_eq!(p.vote(7, Ballot::No, 12), Err(ProposalError::AlreadyVoted));
+
+        assert!(matches!(p.tick(20)[..], [ProposalEvent::Passed(_)]));
+        assert_eq!(p.vote(8, Ballot::Yes, 21), Err(ProposalError::NotVoting(ProposalStatus::Passed)));
+        assert_eq!(p.execute(25).unwrap(), vec![ProposalEvent::Executed]);
+    }
+
+    #[test]
+    fn test_no_quorum_expires_and_cannot_execute() {
+        let mut p = proposal(ProposalKind::ConstitutionalAmendment);
+        p.vote(1, Ballot::Yes, 10).unwrap();
+        let events = p.tick(30);
+        assert!(matches!(events[..], [ProposalEvent::Expired(_)]));
+        assert_eq!(p.execute(31), Err(ProposalError::NotPassed(ProposalStatus::Expired)));
+    }
+}
@@ -0,0 +1,313 @@
+//! Community override of a council veto (GOVERNANCE_CONSTITUTION.md Art. IV §2).
+//!
+//! A fork is ratified by the council, or, once the council has refused it, by
+//! a community vote on the same payload opened after the refusal. The passed
+//! community proposal is what clients get a proof of (`core::fork`): the chain
+//! stores a `CommunityRecord`, which binds the tally and a Merkle root over the
+//! ballots to the fork and to the veto it overrode.
+//!
+//! Supplemental guidelines the council approved are ratified by a community
+//! vote as well; once it closes, the chain stores a `GuidelineRecord` of the
+//! outcome for clients to prove (`guideline`).
+
+use crate::proposal::{Ballot, Proposal, ProposalKind, ProposalStatus, Timestamp};
+use crate::vote::VoteCount;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverrideError {
+    /// The council proposal is not a fork ratification.
+    NotARatification,
+    /// The council has not refused the fork (it is still voting, or passed it).
+    NotVetoed(ProposalStatus),
+    /// The community proposal is not a community ratification.
+    NotAnOverride,
+    /// The two proposals are about different payloads.
+    PayloadMismatch,
+    /// The community vote was opened before the council vote closed.
+    OpenedBeforeVeto,
+    NotPassed(ProposalStatus),
+    /// The ballots given are not the ones the tally counts.
+    BallotsMismatch,
+}
+
+impl fmt::Display for OverrideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverrideError::NotARatification => write!(f, "Council proposal is not a ratification"),
+            OverrideError::NotVetoed(status) => write!(f, "Council has not refused the fork ({:?})", status),
+            OverrideError::NotAnOverride => write!(f, "Proposal is not a community ratification"),
+            OverrideError::PayloadMismatch => write!(f, "Community vote is about a different payload"),
+            OverrideError::OpenedBeforeVeto => write!(f, "Community vote was opened before the council vote closed"),
+            OverrideError::NotPassed(status) => write!(f, "Community vote has not passed ({:?})", status),
+            OverrideError::BallotsMismatch => write!(f, "Ballots do not match the community tally"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OverrideError {}
+
+/// Whether `community` may be opened against the council's refusal `veto`.
+pub fn check_override<V: Ord>(veto: &Proposal<V>, community: &Proposal<V>) -> Result<(), OverrideError> {
+    if veto.kind != ProposalKind::Ratification {
+        return Err(OverrideError::NotARatification);
+    }
+    if !matches!(veto.status, ProposalStatus::Rejected | ProposalStatus::Expired) {
+        return Err(OverrideError::NotVetoed(veto.status));
+    }
+    if community.kind != ProposalKind::CommunityRatification {
+        return Err(OverrideError::NotAnOverride);
+    }
+    if community.payload_hash != veto.payload_hash {
+        return Err(OverrideError::PayloadMismatch);
+    }
+    if community.schedule.created < veto.schedule.closes {
+        return Err(OverrideError::OpenedBeforeVeto);
+    }
+    Ok(())
+}
+
+/// Whether `community` has superseded `veto` and ratified the fork.
+pub fn is_overridden<V: Ord>(veto: &Proposal<V>, community: &Proposal<V>) -> Result<(), OverrideError> {
+    check_override(veto, community)?;
+    match community.status {
+        ProposalStatus::Passed | ProposalStatus::Executed => Ok(()),
+        status => Err(OverrideError::NotPassed(status)),
+    }
+}
+
+/// A community ratification that overrode a council veto, as stored on chain.
+/// Mirrors `core::fork::CommunityProof` minus the state root and storage
+/// proof; clients compare its SCALE encoding field by field, in this order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct CommunityRecord {
+    /// Id of the ratified fork: the payload hash of both proposals.
+    pub fork: [u8; 32],
+    /// Id of the community proposal.
+    pub proposal: u64,
+    pub opened_at: Timestamp,
+    /// Id of the council proposal that refused the fork.
+    pub vetoed_by: u64,
+    /// When the council vote closed.
+    pub vetoed_at: Timestamp,
+    pub yes: u64,
+    pub no: u64,
+    pub abstain: u64,
+    /// Merkle root over every ballot's `ballot_leaf`, ordered by voter.
+    pub ballots_root: [u8; 32],
+}
+
+impl CommunityRecord {
+    /// Record `community` once it has overridden `veto`, with every ballot
+    /// cast in it under the 32-byte account id clients know its voter by
+    /// (`core::fork::CommunityBallot`).
+    pub fn new<V: Ord>(
+        veto: &Proposal<V>,
+        community: &Proposal<V>,
+        ballots: impl IntoIterator<Item = ([u8; 32], Ballot)>,
+    ) -> Result<Self, OverrideError> {
+        is_overridden(veto, community)?;
+        let mut counted = VoteCount::default();
+        let leaves: Vec<[u8; 33]> = ballots
+            .into_iter()
+            .map(|(voter, ballot)| {
+                match ballot {
+                    Ballot::Yes => counted.yes += 1,
+                    Ballot::No => counted.no += 1,
+                    Ballot::Abstain => counted.abstain += 1,
+                }
+                ballot_leaf(&voter, ballot)
+            })
+            .collect();
+        if counted != community.votes {
+            return Err(OverrideError::BallotsMismatch);
+        }
+        Ok(CommunityRecord {
+            fork: community.payload_hash,
+            proposal: community.id,
+            opened_at: community.schedule.created,
+            vetoed_by: veto.id,
+            vetoed_at: veto.schedule.closes,
+            yes: community.votes.yes,
+            no: community.votes.no,
+            abstain: community.votes.abstain,
+            ballots_root: ballots_root(leaves),
+        })
+    }
+}
+
+/// The closed community vote on a guideline draft, as stored on chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct GuidelineRecord {
+    /// `GuidelineDraft::payload_hash` of the draft voted on.
+    pub payload_hash: [u8; 32],
+    /// Id of the community proposal.
+    pub proposal: u64,
+    /// When the vote closed; the guideline cannot take effect earlier.
+    pub closed_at: Timestamp,
+    pub passed: bool,
+}
+
+impl GuidelineRecord {
+    /// Record `community` once it has closed; `None` while it is open.
+    pub fn new<V: Ord>(community: &Proposal<V>) -> Option<Self> {
+        let passed = match community.status {
+            ProposalStatus::Passed | ProposalStatus::Executed => true,
+            ProposalStatus::Rejected | ProposalStatus::Expired => false,
+            ProposalStatus::Deliberation | ProposalStatus::Voting => return None,
+        };
+        Some(GuidelineRecord {
+            payload_hash: community.payload_hash,
+            proposal: community.id,
+            closed_at: community.schedule.closes,
+            passed,
+        })
+    }
+
+    /// The SCALE encoding, which clients compare against a storage proof.
+    pub fn to_bytes(&self) -> [u8; 49] {
+        let mut bytes = [0u8; 49];
+        bytes[..32].copy_from_slice(&self.payload_hash);
+        bytes[32..40].copy_from_slice(&self.proposal.to_le_bytes());
+        bytes[40..48].copy_from_slice(&self.closed_at.to_le_bytes());
+        bytes[48] = self.passed as u8;
+        bytes
+    }
+}
+
+/// Merkle leaf for a ballot: `voter || choice` (0 yes, 1 no, 2 abstain).
+pub fn ballot_leaf(voter: &[u8; 32], ballot: Ballot) -> [u8; 33] {
+    let mut leaf = [0u8; 33];
+    leaf[..32].copy_from_slice(voter);
+    leaf[32] = match ballot {
+        Ballot::Yes => 0,
+        Ballot::No => 1,
+        Ballot::Abstain => 2,
+    };
+    leaf
+}
+
+/// Root of the binary BLAKE3 tree over `leaves`, sorted, as built by
+/// `crypto::merkle` (CRYPTO_PROTOCOL_SPEC.md §5.2). Every leaf starts with a
+/// distinct 32-byte voter, so sorting them orders the ballots by voter, as
+/// `core::fork::CommunityProof` does.
+pub fn ballots_root(mut leaves: Vec<[u8; 33]>) -> [u8; 32] {
+    leaves.sort();
+    let mut level: Vec<[u8; 32]> = leaves.iter().map(|leaf| hash(&[&[0x00], leaf.as_slice()])).collect();
+    if level.is_empty() {
+        return hash(&[]);
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash(&[&[0x01], left, right]),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    *hasher.finalize().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proposal::Schedule;
+
+    fn proposal(kind: ProposalKind, created: u64, eligible: u64) -> Proposal<u32> {
+        let schedule = Schedule { created, opens: created, closes: created + 10 };
+        Proposal::new(0, kind, [3; 32], 0, schedule, kind.default_rule(), eligible).unwrap()
+    }
+
+    #[test]
+    fn test_override_needs_prior_veto() {
+        let mut veto = proposal(ProposalKind::Ratification, 0, 10);
+        let early = proposal(ProposalKind::CommunityRatification, 5, 100);
+        assert_eq!(check_override(&veto, &early), Err(OverrideError::NotVetoed(ProposalStatus::Deliberation)));
+
+        for voter in 0..7 {
+            veto.vote(voter, Ballot::No, 1).unwrap();
+        }
+        veto.tick(10);
+        assert_eq!(check_override(&veto, &early), Err(OverrideError::OpenedBeforeVeto));
+
+        let mut community = proposal(ProposalKind::CommunityRatification, 10, 100);
+        assert!(check_override(&veto, &community).is_ok());
+        community.vote(1, Ballot::Yes, 11).unwrap();
+        community.vote(2, Ballot::Abstain, 11).unwrap();
+        assert_eq!(is_overridden(&veto, &community), Err(OverrideError::NotPassed(ProposalStatus::Voting)));
+        community.tick(20);
+        assert!(is_overridden(&veto, &community).is_ok());
+
+        let ballots = || community.ballots().map(|(v, ballot)| ([*v as u8; 32], ballot));
+        let record = CommunityRecord::new(&veto, &community, ballots()).unwrap();
+        assert_eq!((record.fork, record.vetoed_at, record.opened_at), ([3; 32], 10, 10));
+        assert_eq!((record.yes, record.no, record.abstain), (1, 0, 1));
+        assert_eq!(
+            CommunityRecord::new(&veto, &community, ballots().skip(1)),
+            Err(OverrideError::BallotsMismatch)
+        );
+        assert_eq!(CommunityRecord::new(&veto, &veto, ballots()), Err(OverrideError::NotAnOverride));
+    }
+
+    #[test]
+    fn test_guideline_record() {
+        let mut community = proposal(ProposalKind::CommunityRatification, 10, 100);
+        community.vote(1, Ballot::Yes, 11).unwrap();
+        assert_eq!(GuidelineRecord::new(&community), None);
+        community.tick(20);
+        let record = GuidelineRecord::new(&community).unwrap();
+        assert_eq!(record, GuidelineRecord { payload_hash: [3; 32], proposal: 0, closed_at: 20, passed: true });
+
+        let mut bytes = [3; 49];
+        bytes[32..].copy_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 20, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(record.to_bytes(), bytes);
+        #[cfg(feature = "scale")]
+        assert_eq!(codec::Encode::encode(&record), bytes.to_vec());
+    }
+
+    #[test]
+    fn test_ballots_root_matches_client_tree() {
+        // Roots `crypto::merkle` builds for these leaves, pinned so that the
+        // no_std build the runtime uses is checked too.
+        let leaves: Vec<[u8; 33]> = (0..5u8).rev().map(|v| ballot_leaf(&[v; 32], Ballot::No)).collect();
+        assert_eq!(
+            ballots_root(leaves.clone()),
+            [
+                231, 217, 81, 216, 46, 176, 131, 49, 47, 181, 180, 235, 99, 120, 140, 105, 225, 183, 178, 82, 200, 147,
+                155, 249, 89, 21, 114, 54, 9, 83, 179, 37
+            ]
+        );
+        assert_eq!(
+            ballots_root(Vec::new()),
+            [
+                175, 19, 73, 185, 245, 249, 161, 166, 160, 64, 77, 234, 54, 220, 201, 73, 155, 203, 37, 201, 173, 193,
+                18, 183, 204, 154, 147, 202, 228, 31, 50, 98
+            ]
+        );
+
+        #[cfg(feature = "std")]
+        {
+            let mut sorted = leaves.clone();
+            sorted.sort();
+            let tree = crypto::merkle::MerkleTree::new(&sorted);
+            assert_eq!(ballots_root(leaves), *tree.root().as_bytes());
+            let empty = crypto::merkle::MerkleTree::new(Vec::<Vec<u8>>::new());
+            assert_eq!(ballots_root(Vec::new()), *empty.root().as_bytes());
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Governance state and automatic dissolution (GOVERNANCE_CONSTITUTION.md Art. III).
+//!
+//! ```text
+//! Bootstrap --criteria met--> Electing --council seated--> Active
+//!                                ^                           |
+//!                                +-- election -- Caretaker <-+ dissolution
+//! ```
+
+use crate::council::Council;
+use crate::proposal::ProposalEvent;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub enum GovernanceState {
+    /// Genesis rules apply until the Bootstrap criteria hold (Art. III §4).
+    #[default]
+    Bootstrap,
+    Active,
+    /// Council dissolved; no policy changes or blacklists (Art. III §3).
+    Caretaker,
+    /// A general election is running; caretaker restrictions still apply.
+    Electing,
+}
+
+/// Why the council was dissolved.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum DissolutionReason {
+    /// A council vote closed without quorum (Art. III §1.1).
+    QuorumFailed,
+    /// Fewer than `MIN_COUNCIL_MEMBERS` seats are filled (Art. III §1.2).
+    BelowMinimum,
+    /// A Vote of No Confidence passed (Art. III §2).
+    NoConfidence,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateError {
+    /// Governance has not been activated yet.
+    Bootstrap,
+    /// Council actions are suspended in Caretaker Mode.
+    Caretaker,
+    /// Council actions are suspended while the election runs.
+    Electing,
+    /// The transition is not possible from this state.
+    InvalidTransition(GovernanceState),
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::Bootstrap => write!(f, "Governance is in the Bootstrap Phase"),
+            StateError::Caretaker => write!(f, "Council actions are not allowed in Caretaker Mode"),
+            StateError::Electing => write!(f, "Council actions are not allowed during an election"),
+            StateError::InvalidTransition(state) => write!(f, "Transition not allowed from {:?}", state),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StateError {}
+
+/// The dissolution trigger, if any, after a membership change or a council
+/// proposal event. Only meaningful while the council is `Active`.
+pub fn dissolution_trigger<Id: Ord + Clone>(
+    council: &Council<Id>,
+    event: Option<&ProposalEvent>,
+) -> Option<DissolutionReason> {
+    if !council.has_quorum() {
+        return Some(DissolutionReason::BelowMinimum);
+    }
+    match event {
+        Some(ProposalEvent::Expired(_)) => Some(DissolutionReason::QuorumFailed),
+        _ => None,
+    }
+}
+
+impl GovernanceState {
+    /// Whether the council may propose, vote on or execute decisions.
+    pub fn check_council_action(&self) -> Result<(), StateError> {
+        match self {
+            GovernanceState::Active => Ok(()),
+            GovernanceState::Bootstrap => Err(StateError::Bootstrap),
+            GovernanceState::Caretaker => Err(StateError::Caretaker),
+            GovernanceState::Electing => Err(StateError::Electing),
+        }
+    }
+
+    /// Active -> Caretaker. The caller unseats the council and schedules an
+    /// election.
+    pub fn dissolve(&mut self) -> Result<(), StateError> {
+        self.transition(GovernanceState::Active, GovernanceState::Caretaker)
+    }
+
+    /// Bootstrap or Caretaker -> Electing.
+    pub fn open_election(&mut self) -> Result<(), StateError> {
+        if *self == GovernanceState::Bootstrap {
+            *self = GovernanceState::Electing;
+            return Ok(());
+        }
+        self.transition(GovernanceState::Caretaker, GovernanceState::Electing)
+    }
+
+    /// Electing -> Active, once the elected council meets the minimum size.
+    pub fn seat_council<Id: Ord + Clone>(&mut self, council: &Council<Id>) -> Result<(), StateError> {
+        if !council.has_quorum() {
+            return Err(StateError::InvalidTransition(*self));
+        }
+        self.transition(GovernanceState::Electing, GovernanceState::Active)
+    }
+
+    fn transition(&mut self, from: GovernanceState, to: GovernanceState) -> Result<(), StateError> {
+        if *self != from {
+            return Err(StateError::InvalidTransition(*self));
+        }
+        *self = to;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::council::GeoRegion;
+    use crate::vote::{Outcome, Tally};
+
+    #[test]
+    fn test_dissolution_cycle() {
+        let mut council = Council::new();
+        for id in 0..10u32 {
+            council.seat(id, GeoRegion::Europe, 0, id, false).unwrap();
+        }
+        let mut state = GovernanceState::Bootstrap;
+        assert_eq!(state.check_council_action(), Err(StateError::Bootstrap));
+        state.open_election().unwrap();
+        state.seat_council(&council).unwrap();
+        assert_eq!(dissolution_trigger(&council, None), None);
+
+        let expired = ProposalEvent::Expired(Tally { outcome: Outcome::NoQuorum, quorum_margin: -1, pass_margin: 0 });
+        assert_eq!(dissolution_trigger(&council, Some(&expired)), Some(DissolutionReason::QuorumFailed));
+        council.remove(&0);
+        assert_eq!(dissolution_trigger(&council, None), Some(DissolutionReason::BelowMinimum));
+
+        state.dissolve().unwrap();
+        assert_eq!(state.check_council_action(), Err(StateError::Caretaker));
+        assert_eq!(state.seat_council(&council), Err(StateError::InvalidTransition(GovernanceState::Caretaker)));
+        state.open_election().unwrap();
+        assert_eq!(state.check_council_action(), Err(StateError::Electing));
+    }
+}
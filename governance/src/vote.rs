@@ -1,3 +1,195 @@
+//! Vote counting with exact fractions (GOVERNANCE_CONSTITUTION.md Art. II).
+
+use serde::{Deserialize, Serialize};
+
+/// Exact non-negative fraction used for quorums and thresholds, kept in lowest terms.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct Fraction {
+    numer: u64,
+    denom: u64,
+}
+
+impl Fraction {
+    /// `numer / denom`. Panics if `denom` is zero.
+    pub const fn new(numer: u64, denom: u64) -> Self {
+        assert!(denom != 0, "fraction with zero denominator");
+        let g = gcd(numer, denom);
+        Fraction { numer: numer / g, denom: denom / g }
+    }
+
+    pub const fn from_integer(n: u64) -> Self {
+        Fraction { numer: n, denom: 1 }
+    }
+
+    pub fn numer(&self) -> u64 {
+        self.numer
+    }
+
+    pub fn denom(&self) -> u64 {
+        self.denom
+    }
+}
+
+const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    if a == 0 { 1 } else { a }
+}
+
+/// What the pass threshold is measured against.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub enum Base {
+    /// Yes + no; abstentions are ignored.
+    Decisive,
+    /// Yes + no + abstain; an abstention counts like a no.
+    Present,
+    /// Everyone eligible to vote; not voting counts like a no.
+    Eligible,
+}
+
+/// How a decision is made.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct VoteRule {
+    /// Share of eligible voters who must cast a ballot (abstentions included).
+    pub quorum: Fraction,
+    /// Share of `base` that must vote yes.
+    pub threshold: Fraction,
+    /// `true`: yes must exceed the threshold; `false`: reaching it is enough.
+    pub strict: bool,
+    pub base: Base,
+}
+
+/// Ballots cast in one vote.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, codec::MaxEncodedLen, scale_info::TypeInfo))]
+pub struct VoteCount {
+    pub yes: u64,
+    pub no: u64,
+    pub abstain: u64,
+}
+
+impl VoteCount {
+    pub fn cast(&self) -> u64 {
+        self.yes + self.no + self.abstain
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum Outcome {
+    Passed,
+    Failed,
+    NoQuorum,
+}
+
+/// The result of a tally.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct Tally {
+    pub outcome: Outcome,
+    /// Ballots cast minus ballots required for quorum. Negative without quorum.
+    pub quorum_margin: i64,
+    /// Yes votes minus yes votes required to pass. Negative if the vote failed
+    /// (or would have, had quorum been met).
+    pub pass_margin: i64,
+}
+
+impl VoteRule {
+    /// Count `votes` among `eligible` voters.
+    pub fn tally(&self, votes: &VoteCount, eligible: u64) -> Tally {
+        let required_quorum = ceil_mul(self.quorum, eligible);
+        let quorum_margin = signed_diff(votes.cast(), required_quorum);
+
+        let base = match self.base {
+            Base::Decisive => votes.yes + votes.no,
+            Base::Present => votes.cast(),
+            Base::Eligible => eligible,
+        };
+        let required_yes = if self.strict {
+            floor_mul(self.threshold, base) + 1
+        } else {
+            ceil_mul(self.threshold, base).max(1)
+        };
+        let pass_margin = signed_diff(votes.yes, required_yes);
+
+        let outcome = if quorum_margin < 0 {
+            Outcome::NoQuorum
+        } else if pass_margin >= 0 {
+            Outcome::Passed
+        } else {
+            Outcome::Failed
+        };
+
+        Tally { outcome, quorum_margin, pass_margin }
+    }
+}
+
+/// Simple majority (>50%) of the votes given.
 pub fn majority_vote(votes_in_favor: usize, total_votes: usize) -> bool {
-    votes_in_favor > total_votes / 2
+    let rule = VoteRule {
+        quorum: Fraction::from_integer(0),
+        threshold: Fraction::new(1, 2),
+        strict: true,
+        base: Base::Present,
+    };
+    let votes = VoteCount {
+        yes: votes_in_favor as u64,
+        no: total_votes.saturating_sub(votes_in_favor) as u64,
+        abstain: 0,
+    };
+    rule.tally(&votes, total_votes as u64).outcome == Outcome::Passed
+}
+
+// Decoded fractions skip `Fraction::new`, so a zero denominator is treated as one.
+
+/// `floor(fraction * n)`, computed without rounding error.
+fn floor_mul(fraction: Fraction, n: u64) -> u64 {
+    (fraction.numer() as u128 * n as u128 / fraction.denom().max(1) as u128) as u64
+}
+
+/// `ceil(fraction * n)`, computed without rounding error.
+fn ceil_mul(fraction: Fraction, n: u64) -> u64 {
+    (fraction.numer() as u128 * n as u128).div_ceil(fraction.denom().max(1) as u128) as u64
+}
+
+fn signed_diff(a: u64, b: u64) -> i64 {
+    a as i64 - b as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charter::Decision;
+
+    #[test]
+    fn test_council_quorum_and_majority() {
+        let rule = Decision::CouncilOrdinary.rule();
+
+        // 15 seated: quorum is ceil(0.66 * 15) = 10 ballots
+        let short = VoteCount { yes: 9, no: 0, abstain: 0 };
+        assert_eq!(rule.tally(&short, 15).outcome, Outcome::NoQuorum);
+        assert_eq!(rule.tally(&short, 15).quorum_margin, -1);
+
+        // 10 present: 5 yes is a tie, not a majority
+        let tie = VoteCount { yes: 5, no: 4, abstain: 1 };
+        assert_eq!(rule.tally(&tie, 15), Tally { outcome: Outcome::Failed, quorum_margin: 0, pass_margin: -1 });
+        let won = VoteCount { yes: 6, no: 4, abstain: 0 };
+        assert_eq!(rule.tally(&won, 15).outcome, Outcome::Passed);
+    }
+
+    #[test]
+    fn test_supermajority_and_legacy_majority() {
+        let rule = Decision::ConstitutionalAmendment.rule();
+        // 12 present: 75% is exactly 9
+        assert_eq!(rule.tally(&VoteCount { yes: 9, no: 3, abstain: 0 }, 12).outcome, Outcome::Passed);
+        assert_eq!(rule.tally(&VoteCount { yes: 8, no: 3, abstain: 1 }, 12).outcome, Outcome::Failed);
+
+        assert!(majority_vote(3, 5));
+        assert!(!majority_vote(2, 4));
+        assert!(!majority_vote(0, 0));
+    }
 }
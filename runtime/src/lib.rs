@@ -9,13 +9,16 @@ use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
     generic,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
     MultiSignature,
 };
 
 
 pub mod pallet_governance;
 
+/// Technical content limits, identical to the ones clients enforce.
+pub use platform_core::limits;
+
 
 use frame_support::{
     construct_runtime, parameter_types,
@@ -26,7 +29,9 @@ use frame_system as system;
 use sp_runtime::traits::IdentityLookup;
 use sp_core::H256;
 
-pub type AccountId = u64;
+/// 32-byte account ids, as community ballots name their voters
+/// (`core::fork::CommunityBallot`).
+pub type AccountId = <<MultiSignature as Verify>::Signer as IdentifyAccount>::AccountId;
 pub type BlockNumber = u32;
 pub type Index = u32;
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
@@ -78,6 +83,16 @@ impl system::Config for Runtime {
 impl pallet_governance::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type MinCouncilSize = ConstU32<10>;
+    // The charter sets no maximum; this only bounds storage.
+    type MaxCouncilSize = ConstU32<64>;
+    // About one day at six-second blocks.
+    type ElectionDelay = ConstU32<14_400>;
+    // 90 days (Art. III §2 and §4), counted in one-day buckets.
+    type ActivityWindow = ConstU32<{ 90 * 14_400 }>;
+    type ActivityBucket = ConstU32<14_400>;
+    // 90 days between votes of no confidence, 14 days of voting (Art. III §2).
+    type NoConfidenceCooldown = ConstU32<{ 90 * 14_400 }>;
+    type NoConfidenceVoting = ConstU32<{ 14 * 14_400 }>;
 }
 
 
@@ -6,6 +6,14 @@ pub use pallet::*;
 pub mod pallet_governance {
     use frame_support::{pallet_prelude::*, traits::StorageVersion};
     use frame_system::pallet_prelude::*;
+    use governance::activity::ActivityRecord;
+    use governance::bootstrap::BootstrapProgress;
+    use governance::council::GeoRegion;
+    use governance::no_confidence::{NoConfidence, NoConfidenceError};
+    use governance::proposal::{Ballot, Proposal, ProposalError, ProposalEvent, ProposalKind, ProposalStatus, Schedule};
+    use governance::ratification::{check_override, CommunityRecord, GuidelineRecord, OverrideError};
+    use governance::state::{DissolutionReason, GovernanceState, StateError};
+    use sp_runtime::SaturatedConversion;
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
@@ -13,7 +21,94 @@ pub mod pallet_governance {
 
     #[pallet::storage]
     #[pallet::getter(fn council_members)]
-    pub type CouncilMembers<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+    pub type CouncilMembers<T: Config> = StorageValue<_, BoundedVec<T::AccountId, T::MaxCouncilSize>, ValueQuery>;
+
+    /// Proposals, timed in block numbers.
+    #[pallet::storage]
+    #[pallet::getter(fn proposals)]
+    pub type Proposals<T: Config> = StorageMap<_, Blake2_128Concat, u64, Proposal<T::AccountId>, OptionQuery>;
+
+    /// Ballots on each proposal, by proposal id and voter.
+    #[pallet::storage]
+    pub type Ballots<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u64, Blake2_128Concat, T::AccountId, Ballot, OptionQuery>;
+
+    #[pallet::storage]
+    pub type NextProposalId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn state)]
+    pub type State<T: Config> = StorageValue<_, GovernanceState, ValueQuery>;
+
+    /// How often the council has been dissolved; identifies the council a
+    /// Vote of No Confidence targets.
+    #[pallet::storage]
+    pub type Dissolutions<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Activity (in buckets) and declared region per account.
+    #[pallet::storage]
+    pub type Activity<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ActivityRecord, OptionQuery>;
+
+    /// Accounts whose last activity falls in a bucket, by bucket and declared
+    /// region. A bucket is pruned once it leaves the activity window.
+    #[pallet::storage]
+    pub type BucketActivity<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u64, Blake2_128Concat, Option<GeoRegion>, u64, ValueQuery>;
+
+    /// Active accounts per declared region (`None`: undeclared).
+    #[pallet::storage]
+    pub type ActiveTotals<T: Config> = StorageMap<_, Blake2_128Concat, Option<GeoRegion>, u64, ValueQuery>;
+
+    /// The latest bucket whose start has been processed.
+    #[pallet::storage]
+    pub type RolledBucket<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Accounts active at the end of the previous bucket: the electorate of
+    /// community votes opened in the current one.
+    #[pallet::storage]
+    pub type PreviousBucketActive<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Block at which the next general election opens.
+    #[pallet::storage]
+    pub type ElectionAt<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+    /// Community ratification proposal id to the id of the council veto it
+    /// would override.
+    #[pallet::storage]
+    pub type Overrides<T: Config> = StorageMap<_, Blake2_128Concat, u64, u64, OptionQuery>;
+
+    /// Forks ratified by the community over a council veto, by fork id. Clients
+    /// read these with a storage proof under a finalized state root
+    /// (`core::fork::CommunityProof`), so the storage name and hasher are fixed.
+    #[pallet::storage]
+    #[pallet::getter(fn community_ratifications)]
+    pub type CommunityRatifications<T: Config> = StorageMap<_, Blake2_128Concat, [u8; 32], CommunityRecord, OptionQuery>;
+
+    /// The community vote on each council-approved guideline draft, by
+    /// payload hash. A draft is put to the community once.
+    #[pallet::storage]
+    pub type GuidelineVotes<T: Config> = StorageMap<_, Blake2_128Concat, [u8; 32], u64, OptionQuery>;
+
+    /// Outcomes of closed guideline votes, by payload hash. Clients read these
+    /// with a storage proof under a finalized state root
+    /// (`governance::guideline::GuidelineVote`), so the storage name and hasher
+    /// are fixed.
+    #[pallet::storage]
+    #[pallet::getter(fn guideline_ratifications)]
+    pub type GuidelineRatifications<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], GuidelineRecord, OptionQuery>;
+
+    /// Community Vote of No Confidence: the tally of the open vote, if any,
+    /// and when the last one closed. Timed in block numbers.
+    #[pallet::storage]
+    pub type NoConfidenceVote<T: Config> = StorageValue<_, NoConfidence<T::AccountId>, OptionQuery>;
+
+    /// Ballots in each Vote of No Confidence, by vote id and voter. The
+    /// electorate is everyone active at the end of the bucket before the
+    /// vote opened.
+    #[pallet::storage]
+    pub type NoConfidenceBallots<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u64, Blake2_128Concat, T::AccountId, Ballot, OptionQuery>;
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -21,6 +116,20 @@ pub mod pallet_governance {
         MemberAdded(T::AccountId),
         MemberRemoved(T::AccountId),
         QuorumReached(u32),
+        ProposalCreated(u64, T::AccountId),
+        Proposal(u64, ProposalEvent),
+        StateChanged(GovernanceState),
+        Dissolved(DissolutionReason),
+        ElectionScheduled(BlockNumberFor<T>),
+        /// Active identities and distinct declared regions.
+        BootstrapProgress(u64, u32),
+        NoConfidence(ProposalEvent),
+        /// A Vote of No Confidence closed; `true` if the motion carried.
+        NoConfidenceConcluded(bool),
+        /// A community vote overrode a council veto and ratified the fork.
+        CommunityRatified(CommunityRecord),
+        /// The community vote on a guideline draft closed.
+        GuidelineConcluded(GuidelineRecord),
     }
 
     #[pallet::error]
@@ -28,6 +137,94 @@ pub mod pallet_governance {
         AlreadyMember,
         NotMember,
         QuorumNotMet,
+        UnknownProposal,
+        InvalidSchedule,
+        NotVoting,
+        AlreadyVoted,
+        NotPassed,
+        /// Governance has not been activated yet.
+        BootstrapPhase,
+        /// Council actions are not allowed in Caretaker Mode.
+        CaretakerMode,
+        /// Council actions are not allowed while an election runs.
+        ElectionInProgress,
+        InvalidTransition,
+        /// The last Vote of No Confidence closed less than the cooldown ago.
+        NoConfidenceCooldown,
+        NoConfidenceOpen,
+        NoConfidenceNotOpen,
+        /// The account was not active when the vote was initiated.
+        NotEligible,
+        /// Community votes are opened by their own calls, not `propose`.
+        CommunityProposal,
+        /// The proposal to override is not a council fork ratification.
+        NotARatification,
+        /// The council has not refused the fork.
+        NotVetoed,
+        /// Only accounts active within the window may do this.
+        Inactive,
+        /// The council has `MaxCouncilSize` members.
+        TooManyMembers,
+        /// More ballots were cast than the call was weighed for.
+        BallotCountTooLow,
+        /// The community ratification has already been recorded.
+        AlreadyRecorded,
+        /// The council proposal is not a guideline it has passed.
+        NotAnApprovedGuideline,
+        /// The guideline draft has already been put to the community.
+        AlreadyProposed,
+        /// The community vote is still open.
+        VoteNotClosed,
+    }
+
+    impl<T> From<ProposalError> for Error<T> {
+        fn from(e: ProposalError) -> Self {
+            match e {
+                ProposalError::InvalidSchedule => Error::InvalidSchedule,
+                ProposalError::NotVoting(_) => Error::NotVoting,
+                ProposalError::AlreadyVoted => Error::AlreadyVoted,
+                ProposalError::NotPassed(_) => Error::NotPassed,
+            }
+        }
+    }
+
+    impl<T> From<StateError> for Error<T> {
+        fn from(e: StateError) -> Self {
+            match e {
+                StateError::Bootstrap => Error::BootstrapPhase,
+                StateError::Caretaker => Error::CaretakerMode,
+                StateError::Electing => Error::ElectionInProgress,
+                StateError::InvalidTransition(_) => Error::InvalidTransition,
+            }
+        }
+    }
+
+    impl<T> From<NoConfidenceError> for Error<T> {
+        fn from(e: NoConfidenceError) -> Self {
+            match e {
+                NoConfidenceError::Cooldown { .. } => Error::NoConfidenceCooldown,
+                NoConfidenceError::AlreadyOpen => Error::NoConfidenceOpen,
+                NoConfidenceError::NotOpen => Error::NoConfidenceNotOpen,
+                NoConfidenceError::NotEligible => Error::NotEligible,
+                NoConfidenceError::State(e) => e.into(),
+                NoConfidenceError::Proposal(e) => e.into(),
+            }
+        }
+    }
+
+    impl<T> From<OverrideError> for Error<T> {
+        fn from(e: OverrideError) -> Self {
+            match e {
+                OverrideError::NotARatification => Error::NotARatification,
+                OverrideError::NotVetoed(_) => Error::NotVetoed,
+                OverrideError::NotPassed(_) => Error::NotPassed,
+                // `propose_override` builds the community proposal itself.
+                OverrideError::NotAnOverride
+                | OverrideError::PayloadMismatch
+                | OverrideError::OpenedBeforeVeto
+                | OverrideError::BallotsMismatch => Error::InvalidTransition,
+            }
+        }
     }
 
     #[pallet::config]
@@ -35,6 +232,63 @@ pub mod pallet_governance {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         #[pallet::constant]
         type MinCouncilSize: Get<u32>;
+        #[pallet::constant]
+        type MaxCouncilSize: Get<u32>;
+        /// Blocks between a dissolution or bootstrap completion and the
+        /// opening of the election.
+        #[pallet::constant]
+        type ElectionDelay: Get<BlockNumberFor<Self>>;
+        /// Blocks an account counts as active after its last transaction.
+        #[pallet::constant]
+        type ActivityWindow: Get<BlockNumberFor<Self>>;
+        /// Blocks per activity bucket. Activity is counted per bucket and the
+        /// window rounds down to whole buckets.
+        #[pallet::constant]
+        type ActivityBucket: Get<BlockNumberFor<Self>>;
+        /// Blocks after a Vote of No Confidence closes before another may
+        /// be initiated.
+        #[pallet::constant]
+        type NoConfidenceCooldown: Get<BlockNumberFor<Self>>;
+        /// Blocks a Vote of No Confidence stays open.
+        #[pallet::constant]
+        type NoConfidenceVoting: Get<BlockNumberFor<Self>>;
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let db = T::DbWeight::get();
+            let mut weight = Self::roll_activity();
+            weight = weight.saturating_add(Self::conclude_no_confidence());
+            weight = weight.saturating_add(Self::schedule_first_election());
+
+            weight = weight.saturating_add(db.reads(1));
+            if ElectionAt::<T>::get().is_some_and(|at| at <= now) {
+                ElectionAt::<T>::kill();
+                weight = weight.saturating_add(db.reads_writes(1, 2));
+                if State::<T>::mutate(|s| s.open_election()).is_ok() {
+                    Self::deposit_event(Event::StateChanged(GovernanceState::Electing));
+                }
+            }
+            weight
+        }
+
+        fn integrity_test() {
+            assert!(
+                !T::ActivityBucket::get().is_zero() && T::ActivityWindow::get() >= T::ActivityBucket::get(),
+                "the activity window must span at least one non-empty bucket"
+            );
+            assert!(
+                T::NoConfidenceVoting::get().saturated_into::<u64>() < Self::max_community_voting(),
+                "a Vote of No Confidence must close within the activity window"
+            );
+            assert!(T::MinCouncilSize::get() <= T::MaxCouncilSize::get());
+            assert_eq!(
+                T::AccountId::max_encoded_len(),
+                32,
+                "community ballots name voters by 32-byte account ids"
+            );
+        }
     }
 
     #[pallet::call]
@@ -44,9 +298,14 @@ pub mod pallet_governance {
             ensure_root(origin)?;
             let mut members = CouncilMembers::<T>::get();
             ensure!(!members.contains(&who), Error::<T>::AlreadyMember);
-            members.push(who.clone());
+            members.try_push(who.clone()).map_err(|_| Error::<T>::TooManyMembers)?;
             CouncilMembers::<T>::put(&members);
             Self::deposit_event(Event::MemberAdded(who));
+
+            if State::<T>::get() == GovernanceState::Electing && members.len() as u32 >= T::MinCouncilSize::get() {
+                State::<T>::put(GovernanceState::Active);
+                Self::deposit_event(Event::StateChanged(GovernanceState::Active));
+            }
             Ok(())
         }
 
@@ -58,18 +317,510 @@ pub mod pallet_governance {
             members.retain(|m| m != &who);
             CouncilMembers::<T>::put(&members);
             Self::deposit_event(Event::MemberRemoved(who));
+            Self::check_dissolution(None);
             Ok(())
         }
 
         #[pallet::weight(5_000)]
         pub fn check_quorum(origin: OriginFor<T>) -> DispatchResult {
             ensure_root(origin)?;
+            let count = CouncilMembers::<T>::get().len() as u32;
+            if count >= T::MinCouncilSize::get() {
+                Self::deposit_event(Event::QuorumReached(count));
+                return Ok(());
+            }
+            ensure!(Self::check_dissolution(None), Error::<T>::QuorumNotMet);
+            Ok(())
+        }
+
+        /// Declare the caller's region. Only accounts that are already active
+        /// may declare, so a declaration never creates an identity on its own.
+        #[pallet::weight(10_000)]
+        pub fn declare_region(origin: OriginFor<T>, region: GeoRegion) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_active(&who), Error::<T>::Inactive);
+            Self::note_activity(&who);
+            let mut record = Activity::<T>::get(&who).ok_or(Error::<T>::Inactive)?;
+            if record.region == Some(region) {
+                return Ok(());
+            }
+
+            BucketActivity::<T>::mutate(record.last_active, record.region, |n| *n = n.saturating_sub(1));
+            BucketActivity::<T>::mutate(record.last_active, Some(region), |n| *n += 1);
+            ActiveTotals::<T>::mutate(record.region, |n| *n = n.saturating_sub(1));
+            ActiveTotals::<T>::mutate(Some(region), |n| *n += 1);
+            record.region = Some(region);
+            Activity::<T>::insert(&who, record);
+            Ok(())
+        }
+
+        /// Report progress towards the Bootstrap criteria. The first general
+        /// election is scheduled in `on_initialize` once they all hold.
+        #[pallet::weight(10_000)]
+        pub fn check_bootstrap(origin: OriginFor<T>) -> DispatchResult {
+            ensure_signed(origin)?;
+            let progress = Self::bootstrap_progress();
+            Self::deposit_event(Event::BootstrapProgress(progress.active_identities, progress.regions.len() as u32));
+            Ok(())
+        }
+
+        /// Open a proposal for council vote. Deliberation lasts `deliberation`
+        /// blocks, voting `voting` blocks.
+        #[pallet::weight(10_000)]
+        pub fn propose(
+            origin: OriginFor<T>,
+            kind: ProposalKind,
+            payload_hash: [u8; 32],
+            deliberation: u64,
+            voting: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!kind.is_community(), Error::<T>::CommunityProposal);
+            State::<T>::get().check_council_action().map_err(Error::<T>::from)?;
+            Self::note_activity(&who);
             let members = CouncilMembers::<T>::get();
-            let count = members.len() as u32;
-            ensure!(count >= T::MinCouncilSize::get(), Error::<T>::QuorumNotMet);
-            Self::deposit_event(Event::QuorumReached(count));
+            ensure!(members.contains(&who), Error::<T>::NotMember);
+
+            let now = Self::now();
+            let opens = now.saturating_add(deliberation);
+            let schedule = Schedule { created: now, opens, closes: opens.saturating_add(voting) };
+            let id = NextProposalId::<T>::mutate(|next| {
+                let id = *next;
+                *next += 1;
+                id
+            });
+            let mut proposal = Proposal::new(
+                id,
+                kind,
+                payload_hash,
+                who.clone(),
+                schedule,
+                kind.default_rule(),
+                members.len() as u64,
+            )
+            .map_err(Error::<T>::from)?;
+
+            Self::deposit_event(Event::ProposalCreated(id, who));
+            let events = proposal.tick(now);
+            Self::deposit_proposal_events(&proposal, events);
+            Proposals::<T>::insert(id, proposal);
+            Ok(())
+        }
+
+        /// Open a Community Ratification of the fork the council refused in
+        /// proposal `veto` (Art. IV §2). Voting opens now and lasts `voting`
+        /// blocks; everyone active at the end of the previous bucket makes up
+        /// the electorate.
+        #[pallet::weight(50_000)]
+        pub fn propose_override(origin: OriginFor<T>, veto: u64, voting: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(voting < Self::max_community_voting(), Error::<T>::InvalidSchedule);
+            Self::note_activity(&who);
+            let vetoed = Proposals::<T>::get(veto).ok_or(Error::<T>::UnknownProposal)?;
+
+            let now = Self::now();
+            let schedule = Schedule { created: now, opens: now, closes: now.saturating_add(voting) };
+            let id = NextProposalId::<T>::mutate(|next| {
+                let id = *next;
+                *next += 1;
+                id
+            });
+            let kind = ProposalKind::CommunityRatification;
+            let mut proposal = Proposal::new(
+                id,
+                kind,
+                vetoed.payload_hash,
+                who.clone(),
+                schedule,
+                kind.default_rule(),
+                PreviousBucketActive::<T>::get(),
+            )
+            .map_err(Error::<T>::from)?;
+            check_override(&vetoed, &proposal).map_err(Error::<T>::from)?;
+
+            Self::deposit_event(Event::ProposalCreated(id, who));
+            let events = proposal.tick(now);
+            Self::deposit_proposal_events(&proposal, events);
+            Proposals::<T>::insert(id, proposal);
+            Overrides::<T>::insert(id, veto);
+            Ok(())
+        }
+
+        /// Vote on a proposal: council members on council proposals, the
+        /// electorate snapshotted at opening on community ratifications.
+        #[pallet::weight(10_000)]
+        pub fn vote(origin: OriginFor<T>, id: u64, ballot: Ballot) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let proposal = Proposals::<T>::get(id).ok_or(Error::<T>::UnknownProposal)?;
+            if proposal.kind.is_community() {
+                let snapshot = Self::snapshot_of(proposal.schedule.created).ok_or(Error::<T>::NotEligible)?;
+                ensure!(Self::was_active(&who, snapshot), Error::<T>::NotEligible);
+            } else {
+                State::<T>::get().check_council_action().map_err(Error::<T>::from)?;
+                ensure!(CouncilMembers::<T>::get().contains(&who), Error::<T>::NotMember);
+            }
+            ensure!(!Ballots::<T>::contains_key(id, &who), Error::<T>::AlreadyVoted);
+            Self::note_activity(&who);
+            Proposals::<T>::try_mutate(id, |slot| {
+                let proposal = slot.as_mut().ok_or(Error::<T>::UnknownProposal)?;
+                let events = proposal.cast(ballot, Self::now()).map_err(Error::<T>::from)?;
+                Ballots::<T>::insert(id, &who, ballot);
+                Self::deposit_proposal_events(proposal, events);
+                Ok(())
+            })
+        }
+
+        /// Store the record clients prove a community ratification with, once
+        /// proposal `id` has passed against the veto it was opened on. The
+        /// record commits to every ballot, so the call is weighed by
+        /// `ballot_count`, which must cover the ballots cast.
+        #[pallet::weight(20_000 + 2_000 * (*ballot_count as u64))]
+        pub fn record_override(origin: OriginFor<T>, id: u64, ballot_count: u32) -> DispatchResult {
+            ensure_signed(origin)?;
+            let mut community = Proposals::<T>::get(id).ok_or(Error::<T>::UnknownProposal)?;
+            let veto = Overrides::<T>::get(id).and_then(Proposals::<T>::get).ok_or(Error::<T>::NotARatification)?;
+            ensure!(community.votes.cast() <= ballot_count as u64, Error::<T>::BallotCountTooLow);
+            ensure!(!CommunityRatifications::<T>::contains_key(community.payload_hash), Error::<T>::AlreadyRecorded);
+
+            let events = community.tick(Self::now());
+            Self::deposit_proposal_events(&community, events);
+            let ballots = Ballots::<T>::iter_prefix(id).map(|(who, ballot)| (Self::voter_id(&who), ballot));
+            let record = CommunityRecord::new(&veto, &community, ballots).map_err(Error::<T>::from)?;
+            Proposals::<T>::insert(id, community);
+            CommunityRatifications::<T>::insert(record.fork, record.clone());
+            Self::deposit_event(Event::CommunityRatified(record));
+            Ok(())
+        }
+
+        /// Put the guideline draft the council passed in proposal `approval` to
+        /// a community ratification vote (Art. I §3.2). Voting opens now and
+        /// lasts `voting` blocks; everyone active at the end of the previous
+        /// bucket makes up the electorate.
+        #[pallet::weight(20_000)]
+        pub fn propose_guideline(origin: OriginFor<T>, approval: u64, voting: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(voting < Self::max_community_voting(), Error::<T>::InvalidSchedule);
+            Self::note_activity(&who);
+            let approved = Proposals::<T>::get(approval).ok_or(Error::<T>::UnknownProposal)?;
+            ensure!(
+                approved.kind == ProposalKind::Guideline
+                    && matches!(approved.status, ProposalStatus::Passed | ProposalStatus::Executed),
+                Error::<T>::NotAnApprovedGuideline
+            );
+            ensure!(!GuidelineVotes::<T>::contains_key(approved.payload_hash), Error::<T>::AlreadyProposed);
+
+            let now = Self::now();
+            let schedule = Schedule { created: now, opens: now, closes: now.saturating_add(voting) };
+            let id = NextProposalId::<T>::mutate(|next| {
+                let id = *next;
+                *next += 1;
+                id
+            });
+            let kind = ProposalKind::CommunityRatification;
+            let mut proposal = Proposal::new(
+                id,
+                kind,
+                approved.payload_hash,
+                who.clone(),
+                schedule,
+                kind.default_rule(),
+                PreviousBucketActive::<T>::get(),
+            )
+            .map_err(Error::<T>::from)?;
+
+            Self::deposit_event(Event::ProposalCreated(id, who));
+            let events = proposal.tick(now);
+            Self::deposit_proposal_events(&proposal, events);
+            Proposals::<T>::insert(id, proposal);
+            GuidelineVotes::<T>::insert(approved.payload_hash, id);
+            Ok(())
+        }
+
+        /// Store the record clients prove the outcome of a guideline vote with,
+        /// once the vote on the draft with `payload_hash` has closed.
+        #[pallet::weight(20_000)]
+        pub fn record_guideline(origin: OriginFor<T>, payload_hash: [u8; 32]) -> DispatchResult {
+            ensure_signed(origin)?;
+            let id = GuidelineVotes::<T>::get(payload_hash).ok_or(Error::<T>::UnknownProposal)?;
+            ensure!(!GuidelineRatifications::<T>::contains_key(payload_hash), Error::<T>::AlreadyRecorded);
+            let mut community = Proposals::<T>::get(id).ok_or(Error::<T>::UnknownProposal)?;
+
+            let events = community.tick(Self::now());
+            Self::deposit_proposal_events(&community, events);
+            let record = GuidelineRecord::new(&community).ok_or(Error::<T>::VoteNotClosed)?;
+            Proposals::<T>::insert(id, community);
+            GuidelineRatifications::<T>::insert(payload_hash, record.clone());
+            Self::deposit_event(Event::GuidelineConcluded(record));
+            Ok(())
+        }
+
+        /// Initiate a Community Vote of No Confidence. Everyone active at the
+        /// end of the previous bucket makes up the electorate; the initiator
+        /// must be among them.
+        #[pallet::weight(10_000)]
+        pub fn initiate_no_confidence(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let now = Self::now();
+            let snapshot = Self::snapshot_of(now).ok_or(Error::<T>::NotEligible)?;
+            ensure!(Self::was_active(&who, snapshot), Error::<T>::NotEligible);
+
+            let mut vote = Self::no_confidence();
+            let events = vote
+                .open(who.clone(), PreviousBucketActive::<T>::get(), Dissolutions::<T>::get(), &State::<T>::get(), now)
+                .map_err(Error::<T>::from)?;
+            NoConfidenceVote::<T>::put(vote);
+            Self::note_activity(&who);
+            for event in events {
+                Self::deposit_event(Event::NoConfidence(event));
+            }
             Ok(())
         }
+
+        #[pallet::weight(10_000)]
+        pub fn vote_no_confidence(origin: OriginFor<T>, ballot: Ballot) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut vote = Self::no_confidence();
+            let (id, opened) = vote
+                .current()
+                .map(|p| (p.id, p.schedule.created))
+                .ok_or(Error::<T>::NoConfidenceNotOpen)?;
+            let snapshot = Self::snapshot_of(opened).ok_or(Error::<T>::NotEligible)?;
+            ensure!(Self::was_active(&who, snapshot), Error::<T>::NotEligible);
+            ensure!(!NoConfidenceBallots::<T>::contains_key(id, &who), Error::<T>::AlreadyVoted);
+
+            let events = vote.cast(ballot, Self::now()).map_err(Error::<T>::from)?;
+            NoConfidenceVote::<T>::put(vote);
+            NoConfidenceBallots::<T>::insert(id, &who, ballot);
+            Self::note_activity(&who);
+            for event in events {
+                Self::deposit_event(Event::NoConfidence(event));
+            }
+            Ok(())
+        }
+
+        /// Advance a proposal to the current block, e.g. to close it.
+        #[pallet::weight(5_000)]
+        pub fn tick(origin: OriginFor<T>, id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+            Proposals::<T>::try_mutate(id, |slot| {
+                let proposal = slot.as_mut().ok_or(Error::<T>::UnknownProposal)?;
+                let events = proposal.tick(Self::now());
+                Self::deposit_proposal_events(proposal, events);
+                Ok(())
+            })
+        }
+
+        #[pallet::weight(10_000)]
+        pub fn execute(origin: OriginFor<T>, id: u64) -> DispatchResult {
+            ensure_root(origin)?;
+            State::<T>::get().check_council_action().map_err(Error::<T>::from)?;
+            Proposals::<T>::try_mutate(id, |slot| {
+                let proposal = slot.as_mut().ok_or(Error::<T>::UnknownProposal)?;
+                let events = proposal.execute(Self::now()).map_err(Error::<T>::from)?;
+                Self::deposit_proposal_events(proposal, events);
+                Ok(())
+            })
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Record an on-chain transaction by `who`. Other pallets call this
+        /// for their own extrinsics.
+        pub fn note_activity(who: &T::AccountId) {
+            let (bucket, window) = (Self::bucket(), Self::window());
+            let previous = Activity::<T>::get(who);
+            let mut record = previous.unwrap_or_else(|| ActivityRecord::new(bucket));
+            match previous {
+                Some(r) if r.last_active >= bucket => return,
+                // Still counted as active: move it to the current bucket.
+                Some(r) if r.is_active(bucket, window) => {
+                    BucketActivity::<T>::mutate(r.last_active, r.region, |n| *n = n.saturating_sub(1))
+                }
+                // New or expired: it becomes active again.
+                _ => ActiveTotals::<T>::mutate(record.region, |n| *n += 1),
+            }
+            BucketActivity::<T>::mutate(bucket, record.region, |n| *n += 1);
+            record.record(bucket, window);
+            Activity::<T>::insert(who, record);
+        }
+
+        /// Progress towards governance activation at the current block.
+        pub fn bootstrap_progress() -> BootstrapProgress {
+            BootstrapProgress::from_totals(ActiveTotals::<T>::iter())
+        }
+
+        fn now() -> u64 {
+            frame_system::Pallet::<T>::block_number().saturated_into::<u64>()
+        }
+
+        fn bucket_length() -> u64 {
+            T::ActivityBucket::get().saturated_into::<u64>()
+        }
+
+        /// The current activity bucket.
+        fn bucket() -> u64 {
+            Self::now() / Self::bucket_length()
+        }
+
+        /// The activity window in buckets.
+        fn window() -> u64 {
+            T::ActivityWindow::get().saturated_into::<u64>() / Self::bucket_length()
+        }
+
+        /// The bucket whose end fixes the electorate of a community vote
+        /// opened at block `opened`.
+        fn snapshot_of(opened: u64) -> Option<u64> {
+            (opened / Self::bucket_length()).checked_sub(1)
+        }
+
+        /// Longest community vote, in blocks, whose electorate activity
+        /// records still reproduce when it closes.
+        fn max_community_voting() -> u64 {
+            Self::window().saturating_sub(2).saturating_mul(Self::bucket_length())
+        }
+
+        /// Whether `who` transacted within the activity window.
+        fn is_active(who: &T::AccountId) -> bool {
+            Activity::<T>::get(who).is_some_and(|r| r.is_active(Self::bucket(), Self::window()))
+        }
+
+        /// Whether `who` was active at the end of `bucket`, less than a
+        /// window ago.
+        fn was_active(who: &T::AccountId, bucket: u64) -> bool {
+            Activity::<T>::get(who).is_some_and(|r| r.was_active(bucket, Self::window()))
+        }
+
+        /// On the first block of a bucket, snapshot the active count and
+        /// stop counting the accounts whose last activity has just left the
+        /// window. Processes at most one bucket, and at most one entry per
+        /// region, per block.
+        fn roll_activity() -> Weight {
+            let db = T::DbWeight::get();
+            let rolled = RolledBucket::<T>::get();
+            if rolled >= Self::bucket() {
+                return db.reads(1);
+            }
+            let next = rolled + 1;
+            RolledBucket::<T>::put(next);
+            let mut active = 0u64;
+            let mut weight = db.reads_writes(1, 2);
+            for (_, count) in ActiveTotals::<T>::iter() {
+                active = active.saturating_add(count);
+                weight = weight.saturating_add(db.reads(1));
+            }
+            PreviousBucketActive::<T>::put(active);
+
+            if let Some(expired) = next.checked_sub(Self::window()) {
+                for (region, count) in BucketActivity::<T>::drain_prefix(expired) {
+                    ActiveTotals::<T>::mutate(region, |n| *n = n.saturating_sub(count));
+                    weight = weight.saturating_add(db.reads_writes(2, 2));
+                }
+            }
+            weight
+        }
+
+        /// Schedule the first general election once the Bootstrap criteria
+        /// hold (Art. III §4).
+        fn schedule_first_election() -> Weight {
+            let db = T::DbWeight::get();
+            if State::<T>::get() != GovernanceState::Bootstrap || ElectionAt::<T>::get().is_some() {
+                return db.reads(2);
+            }
+            let progress = Self::bootstrap_progress();
+            // One totals entry per region plus the undeclared one.
+            let weight = db.reads(2 + progress.regions.len() as u64 + 1);
+            if !progress.is_complete() {
+                return weight;
+            }
+            let at = frame_system::Pallet::<T>::block_number() + T::ElectionDelay::get();
+            ElectionAt::<T>::put(at);
+            Self::deposit_event(Event::ElectionScheduled(at));
+            weight.saturating_add(db.writes(1))
+        }
+
+        fn deposit_proposal_events(proposal: &Proposal<T::AccountId>, events: Vec<ProposalEvent>) {
+            for event in events {
+                if !proposal.kind.is_community() {
+                    Self::check_dissolution(Some(&event));
+                }
+                Self::deposit_event(Event::Proposal(proposal.id, event));
+            }
+        }
+
+        /// The id clients know `who` by in community ballots: its 32-byte
+        /// encoding, as `integrity_test` ensures.
+        fn voter_id(who: &T::AccountId) -> [u8; 32] {
+            who.using_encoded(|bytes| <[u8; 32]>::try_from(bytes).unwrap_or_default())
+        }
+
+        fn no_confidence() -> NoConfidence<T::AccountId> {
+            NoConfidenceVote::<T>::get().unwrap_or_else(|| {
+                NoConfidence::with_periods(
+                    T::NoConfidenceCooldown::get().saturated_into::<u64>(),
+                    T::NoConfidenceVoting::get().saturated_into::<u64>(),
+                )
+            })
+        }
+
+        /// Close the Vote of No Confidence once its window has passed and
+        /// dissolve the council if the motion carried (Art. III §2).
+        fn conclude_no_confidence() -> Weight {
+            let db = T::DbWeight::get();
+            let Some(mut vote) = NoConfidenceVote::<T>::get() else {
+                return db.reads(1);
+            };
+            let Some(passed) = vote.conclude(Self::now()) else {
+                return db.reads(1);
+            };
+            NoConfidenceVote::<T>::put(vote);
+            Self::deposit_event(Event::NoConfidenceConcluded(passed));
+            if !passed {
+                return db.reads_writes(1, 1);
+            }
+            Self::dissolve(DissolutionReason::NoConfidence);
+            // `dissolve` reads the state and the vote, and writes them, the
+            // council, the dissolution count and the election block.
+            db.reads_writes(3, 6)
+        }
+
+        /// Evaluate the Art. III §1 triggers after a membership change or
+        /// proposal event, dissolving the council if one holds. Returns
+        /// whether the council was dissolved.
+        fn check_dissolution(event: Option<&ProposalEvent>) -> bool {
+            if State::<T>::get() != GovernanceState::Active {
+                return false;
+            }
+            let reason = if (CouncilMembers::<T>::get().len() as u32) < T::MinCouncilSize::get() {
+                DissolutionReason::BelowMinimum
+            } else if matches!(event, Some(ProposalEvent::Expired(_))) {
+                DissolutionReason::QuorumFailed
+            } else {
+                return false;
+            };
+            Self::dissolve(reason);
+            true
+        }
+
+        /// Unseat the council, enter Caretaker Mode and schedule an election.
+        /// A Vote of No Confidence still open against the council is dropped,
+        /// so it can never unseat the next one.
+        fn dissolve(reason: DissolutionReason) {
+            if State::<T>::mutate(|s| s.dissolve()).is_err() {
+                return;
+            }
+            CouncilMembers::<T>::kill();
+            Dissolutions::<T>::mutate(|n| *n = n.saturating_add(1));
+            NoConfidenceVote::<T>::mutate(|vote| {
+                if let Some(vote) = vote {
+                    vote.cancel();
+                }
+            });
+            let at = frame_system::Pallet::<T>::block_number() + T::ElectionDelay::get();
+            ElectionAt::<T>::put(at);
+            Self::deposit_event(Event::Dissolved(reason));
+            Self::deposit_event(Event::StateChanged(GovernanceState::Caretaker));
+            Self::deposit_event(Event::ElectionScheduled(at));
+        }
     }
 
     #[pallet::genesis_config]
@@ -80,7 +831,12 @@ pub mod pallet_governance {
     #[pallet::genesis_build]
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
-            CouncilMembers::<T>::put(&self.initial_members);
+            let members: BoundedVec<_, T::MaxCouncilSize> =
+                self.initial_members.clone().try_into().expect("more initial members than MaxCouncilSize");
+            CouncilMembers::<T>::put(members);
+            if self.initial_members.len() as u32 >= T::MinCouncilSize::get() {
+                State::<T>::put(GovernanceState::Active);
+            }
         }
     }
 }